@@ -49,6 +49,18 @@ pub enum Commands {
 
         #[arg(short, long, help = "Force update all tasks regardless of detected changes")]
         force: bool,
+
+        #[arg(
+            long,
+            help = "Offer to link existing Motion tasks to Linear issues before creating new ones"
+        )]
+        adopt: bool,
+
+        #[arg(
+            long,
+            help = "Delete Motion tasks even when the deletion guard would hold them"
+        )]
+        confirm_deletions: bool,
     },
 
     /// Query daemon status
@@ -61,6 +73,13 @@ pub enum Commands {
     Tasks {
         #[arg(short, long, help = "Output waybar JSON format (current + next task)")]
         waybar: bool,
+
+        #[arg(
+            long,
+            help = "Only show tasks Motion can't finish before their deadline",
+            conflicts_with = "waybar"
+        )]
+        at_risk: bool,
     },
 
     /// Complete a Motion task
@@ -72,6 +91,56 @@ pub enum Commands {
         current: bool,
     },
 
+    /// Compare Motion tasks with the stored Linear state
+    Drift {
+        #[arg(long, help = "Write the expected values back to Motion")]
+        repair: bool,
+
+        #[arg(short, long, help = "Filter by sync source name")]
+        source: Option<String>,
+
+        #[arg(long, help = "Output the drift report as JSON")]
+        json: bool,
+    },
+
+    /// Rebuild mappings from the synced tasks in Motion
+    Reconcile {
+        #[arg(long, help = "Only report what would change")]
+        dry_run: bool,
+
+        #[arg(long, help = "Delete duplicate and orphaned Motion tasks")]
+        delete: bool,
+
+        #[arg(
+            long,
+            requires = "delete",
+            help = "Delete even when the deletion guard would hold the tasks"
+        )]
+        confirm_deletions: bool,
+    },
+
+    /// Restore a Motion task deleted by the sync, or list the ones that can be restored
+    #[command(alias = "undo")]
+    Restore {
+        #[arg(help = "Motion task ID or Linear identifier of the deleted task")]
+        target: Option<String>,
+    },
+
+    /// Sync an issue again after its Motion task was deleted in Motion
+    Resume {
+        #[arg(help = "Linear issue identifier, e.g. ENG-123")]
+        identifier: String,
+    },
+
+    /// Show what the sync did to a Linear issue over time
+    History {
+        #[arg(help = "Linear issue identifier, e.g. ENG-123")]
+        identifier: String,
+
+        #[arg(long, help = "Output the events as JSON")]
+        json: bool,
+    },
+
     /// List all tracked issues and metadata in local database
     List {
         #[arg(short, long, help = "Show detailed information for each entry")]
@@ -80,4 +149,49 @@ pub enum Commands {
         #[arg(short, long, help = "Filter by sync source name")]
         source: Option<String>,
     },
+
+    /// Export, import or back up the local database
+    Db {
+        #[command(subcommand)]
+        command: DbCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DbCommands {
+    /// Write every database record as JSON Lines
+    Export {
+        #[arg(help = "Output file (defaults to stdout)")]
+        output: Option<String>,
+    },
+
+    /// Restore records from an export
+    Import {
+        #[arg(help = "Export file to read")]
+        input: String,
+
+        #[arg(
+            long,
+            help = "Replace existing records with the imported ones",
+            conflicts_with = "newer"
+        )]
+        overwrite: bool,
+
+        #[arg(
+            long,
+            help = "Replace existing records only when the imported one is more recent",
+            conflicts_with = "overwrite"
+        )]
+        newer: bool,
+    },
+
+    /// Write a timestamped export next to the database
+    Backup {
+        #[arg(
+            short,
+            long,
+            help = "Directory for the backup (defaults to the backups directory beside the database)"
+        )]
+        dir: Option<String>,
+    },
 }
@@ -1,3 +1,4 @@
+use crate::config::models::EstimationScale;
 use crate::{Error, Result};
 use chrono::{DateTime, Utc};
 use reqwest::{
@@ -7,12 +8,11 @@ use reqwest::{
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, warn};
 
 #[derive(Debug, Clone)]
 pub struct LinearClient {
     client: Client,
-    api_key: String,
     base_url: String,
 }
 
@@ -26,6 +26,7 @@ pub struct LinearIssue {
     pub assignee: Option<User>,
     pub team: Team,
     pub project: Option<Project>,
+    pub cycle: Option<Cycle>,
     pub priority: Option<u32>,
     pub estimate: Option<f64>,
     pub created_at: DateTime<Utc>,
@@ -35,6 +36,23 @@ pub struct LinearIssue {
     pub labels: Vec<IssueLabel>,
 }
 
+/// Fields to change on an issue, unset fields are left alone
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IssueUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+    #[serde(rename = "dueDate", skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+}
+
+impl IssueUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.estimate.is_none() && self.priority.is_none() && self.due_date.is_none()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowState {
     pub id: String,
@@ -42,6 +60,18 @@ pub struct WorkflowState {
     pub state_type: String, // "backlog", "unstarted", "started", "completed", "canceled"
 }
 
+impl WorkflowState {
+    /// Whether the issue is done, canceled or marked as a duplicate
+    pub fn is_terminal(&self) -> bool {
+        matches!(self.state_type.as_str(), "completed" | "canceled")
+    }
+
+    /// Linear marks duplicates by moving them to a canceled-type "Duplicate" state
+    pub fn is_duplicate(&self) -> bool {
+        self.state_type == "canceled" && self.name.eq_ignore_ascii_case("duplicate")
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub id: String,
@@ -54,6 +84,26 @@ pub struct Team {
     pub id: String,
     pub name: String,
     pub key: String,
+    #[serde(rename = "issueEstimationType")]
+    pub issue_estimation_type: Option<String>,
+    #[serde(rename = "issueEstimationExtended", default)]
+    pub issue_estimation_extended: bool,
+    #[serde(rename = "issueEstimationAllowZero", default)]
+    pub issue_estimation_allow_zero: bool,
+}
+
+impl Team {
+    /// The team's estimation scale, if it was fetched and estimates are in use
+    pub fn estimation_scale(&self) -> Option<EstimationScale> {
+        match self.issue_estimation_type.as_deref() {
+            None | Some("notUsed") => None,
+            Some(kind) => Some(EstimationScale {
+                kind: kind.to_string(),
+                extended: self.issue_estimation_extended,
+                allow_zero: self.issue_estimation_allow_zero,
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +112,18 @@ pub struct Project {
     pub name: String,
     pub description: Option<String>,
     pub state: String,
+    #[serde(rename = "targetDate")]
+    pub target_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cycle {
+    pub id: String,
+    pub number: f64,
+    #[serde(rename = "startsAt")]
+    pub starts_at: DateTime<Utc>,
+    #[serde(rename = "endsAt")]
+    pub ends_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -86,14 +148,6 @@ struct GraphQLResponse<T> {
 #[derive(Debug, Deserialize)]
 struct GraphQLError {
     message: String,
-    locations: Option<Vec<GraphQLLocation>>,
-    path: Option<Vec<Value>>,
-}
-
-#[derive(Debug, Deserialize)]
-struct GraphQLLocation {
-    line: u32,
-    column: u32,
 }
 
 #[derive(Deserialize)]
@@ -101,6 +155,121 @@ struct LabelsConnection {
     nodes: Vec<IssueLabel>,
 }
 
+/// Field selection for issue queries, appended after the `issues(...) {` prefix
+const ISSUE_NODES_SELECTION: &str = r#"
+                        nodes {
+                            id
+                            identifier
+                            title
+                            description
+                            state {
+                                id
+                                name
+                                type
+                            }
+                            assignee {
+                                id
+                                name
+                                email
+                            }
+                            team {
+                                id
+                                name
+                                key
+                                issueEstimationType
+                                issueEstimationExtended
+                                issueEstimationAllowZero
+                            }
+                            project {
+                                id
+                                name
+                                description
+                                state
+                                targetDate
+                            }
+                            cycle {
+                                id
+                                number
+                                startsAt
+                                endsAt
+                            }
+                            priority
+                            estimate
+                            createdAt
+                            updatedAt
+                            dueDate
+                            completedAt
+                            labels {
+                                nodes {
+                                    id
+                                    name
+                                    color
+                                }
+                            }
+                        }
+                    }
+                }
+            "#;
+
+#[derive(Deserialize)]
+struct LinearIssueRaw {
+    id: String,
+    identifier: String,
+    title: String,
+    description: Option<String>,
+    state: WorkflowStateRaw,
+    assignee: Option<User>,
+    team: Team,
+    project: Option<Project>,
+    cycle: Option<Cycle>,
+    priority: Option<u32>,
+    estimate: Option<f64>,
+    #[serde(rename = "createdAt")]
+    created_at: DateTime<Utc>,
+    #[serde(rename = "updatedAt")]
+    updated_at: DateTime<Utc>,
+    #[serde(rename = "dueDate")]
+    due_date: Option<String>,
+    #[serde(rename = "completedAt")]
+    completed_at: Option<DateTime<Utc>>,
+    labels: LabelsConnection,
+}
+
+#[derive(Deserialize)]
+struct WorkflowStateRaw {
+    id: String,
+    name: String,
+    #[serde(rename = "type")]
+    state_type: String,
+}
+
+impl From<LinearIssueRaw> for LinearIssue {
+    fn from(raw: LinearIssueRaw) -> Self {
+        LinearIssue {
+            id: raw.id,
+            identifier: raw.identifier,
+            title: raw.title,
+            description: raw.description,
+            state: WorkflowState {
+                id: raw.state.id,
+                name: raw.state.name,
+                state_type: raw.state.state_type,
+            },
+            assignee: raw.assignee,
+            team: raw.team,
+            project: raw.project,
+            cycle: raw.cycle,
+            priority: raw.priority,
+            estimate: raw.estimate,
+            created_at: raw.created_at,
+            updated_at: raw.updated_at,
+            due_date: raw.due_date,
+            completed_at: raw.completed_at,
+            labels: raw.labels.nodes,
+        }
+    }
+}
+
 impl LinearClient {
     pub fn new(api_key: String) -> Result<Self> {
         let mut headers = HeaderMap::new();
@@ -110,7 +279,6 @@ impl LinearClient {
 
         Ok(Self {
             client,
-            api_key,
             base_url: "https://api.linear.app/graphql".to_string(),
         })
     }
@@ -232,54 +400,47 @@ impl LinearClient {
             (query, variables)
         };
 
-        let full_query = format!(
-            "{}{}",
-            query,
-            r#"
-                        nodes {
-                            id
-                            identifier
-                            title
-                            description
-                            state {
-                                id
-                                name
-                                type
-                            }
-                            assignee {
-                                id
-                                name
-                                email
-                            }
-                            team {
-                                id
-                                name
-                                key
-                            }
-                            project {
-                                id
-                                name
-                                description
-                                state
-                            }
-                            priority
-                            estimate
-                            createdAt
-                            updatedAt
-                            dueDate
-                            completedAt
-                            labels {
-                                nodes {
-                                    id
-                                    name
-                                    color
-                                }
-                            }
-                        }
-                    }
-                }
-            "#
+        let issues = self.query_issues(query, variables).await?;
+
+        debug!("Found {} assigned issues", issues.len());
+        Ok(issues)
+    }
+
+    /// Fetch issues by ID regardless of assignee or state, e.g. to see why they left the assigned set
+    pub async fn get_issues_by_ids(&self, issue_ids: &[String]) -> Result<Vec<LinearIssue>> {
+        if issue_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query = r#"
+                query GetIssuesByIds($issueIds: [ID!]) {
+                    issues(
+                        filter: { id: { in: $issueIds } }
+                        first: 100
+                        includeArchived: true
+                    ) {"#;
+
+        let mut issues = Vec::new();
+        for chunk in issue_ids.chunks(100) {
+            let variables = Some(serde_json::json!({ "issueIds": chunk }));
+            issues.extend(self.query_issues(query, variables).await?);
+        }
+
+        debug!(
+            "Found {} of {} requested issues",
+            issues.len(),
+            issue_ids.len()
         );
+        Ok(issues)
+    }
+
+    /// Run an `issues(...) {` query prefix with the full issue field selection
+    async fn query_issues(
+        &self,
+        query: &str,
+        variables: Option<Value>,
+    ) -> Result<Vec<LinearIssue>> {
+        let full_query = format!("{}{}", query, ISSUE_NODES_SELECTION);
 
         #[derive(Deserialize)]
         struct IssuesResponse {
@@ -291,68 +452,14 @@ impl LinearClient {
             nodes: Vec<LinearIssueRaw>,
         }
 
-        #[derive(Deserialize)]
-        struct LinearIssueRaw {
-            id: String,
-            identifier: String,
-            title: String,
-            description: Option<String>,
-            state: WorkflowStateRaw,
-            assignee: Option<User>,
-            team: Team,
-            project: Option<Project>,
-            priority: Option<u32>,
-            estimate: Option<f64>,
-            #[serde(rename = "createdAt")]
-            created_at: DateTime<Utc>,
-            #[serde(rename = "updatedAt")]
-            updated_at: DateTime<Utc>,
-            #[serde(rename = "dueDate")]
-            due_date: Option<String>,
-            #[serde(rename = "completedAt")]
-            completed_at: Option<DateTime<Utc>>,
-            labels: LabelsConnection,
-        }
-
-        #[derive(Deserialize)]
-        struct WorkflowStateRaw {
-            id: String,
-            name: String,
-            #[serde(rename = "type")]
-            state_type: String,
-        }
-
         let response: IssuesResponse = self.execute_query(&full_query, variables).await?;
 
-        let issues: Vec<LinearIssue> = response
+        Ok(response
             .issues
             .nodes
             .into_iter()
-            .map(|raw| LinearIssue {
-                id: raw.id,
-                identifier: raw.identifier,
-                title: raw.title,
-                description: raw.description,
-                state: WorkflowState {
-                    id: raw.state.id,
-                    name: raw.state.name,
-                    state_type: raw.state.state_type,
-                },
-                assignee: raw.assignee,
-                team: raw.team,
-                project: raw.project,
-                priority: raw.priority,
-                estimate: raw.estimate,
-                created_at: raw.created_at,
-                updated_at: raw.updated_at,
-                due_date: raw.due_date,
-                completed_at: raw.completed_at,
-                labels: raw.labels.nodes,
-            })
-            .collect();
-
-        debug!("Found {} assigned issues", issues.len());
-        Ok(issues)
+            .map(LinearIssue::from)
+            .collect())
     }
 
     pub async fn add_label_to_issue(&self, issue_id: &str, label_name: &str) -> Result<()> {
@@ -397,6 +504,359 @@ impl LinearClient {
         Ok(())
     }
 
+    pub async fn remove_label_from_issue(&self, issue_id: &str, label_name: &str) -> Result<()> {
+        // Nothing to remove if the label doesn't exist in the workspace
+        let Some(label_id) = self.find_label(label_name).await? else {
+            return Ok(());
+        };
+
+        let query = r#"
+            mutation RemoveLabelFromIssue($issueId: String!, $labelId: String!) {
+                issueRemoveLabel(id: $issueId, labelId: $labelId) {
+                    success
+                }
+            }
+        "#;
+
+        let mut variables = HashMap::new();
+        variables.insert("issueId", Value::String(issue_id.to_string()));
+        variables.insert("labelId", Value::String(label_id));
+
+        #[derive(Deserialize)]
+        struct RemoveLabelResponse {
+            #[serde(rename = "issueRemoveLabel")]
+            issue_remove_label: MutationResponse,
+        }
+
+        #[derive(Deserialize)]
+        struct MutationResponse {
+            success: bool,
+        }
+
+        let response: RemoveLabelResponse = self
+            .execute_query(query, Some(serde_json::to_value(variables)?))
+            .await?;
+
+        if !response.issue_remove_label.success {
+            warn!(
+                "Failed to remove label '{}' from issue {}",
+                label_name, issue_id
+            );
+            return Err(Error::LinearApi {
+                message: format!("Failed to remove label '{}' from issue", label_name),
+            });
+        }
+
+        debug!("Removed label '{}' from issue {}", label_name, issue_id);
+        Ok(())
+    }
+
+    /// Update scalar fields of an issue, returning its new `updatedAt`
+    pub async fn update_issue(
+        &self,
+        issue_id: &str,
+        update: &IssueUpdate,
+    ) -> Result<DateTime<Utc>> {
+        let query = r#"
+            mutation UpdateIssue($issueId: String!, $input: IssueUpdateInput!) {
+                issueUpdate(id: $issueId, input: $input) {
+                    success
+                    issue {
+                        updatedAt
+                    }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "issueId": issue_id,
+            "input": update,
+        });
+
+        #[derive(Deserialize)]
+        struct IssueUpdateResponse {
+            #[serde(rename = "issueUpdate")]
+            issue_update: IssueUpdateMutation,
+        }
+
+        #[derive(Deserialize)]
+        struct IssueUpdateMutation {
+            success: bool,
+            issue: Option<UpdatedIssue>,
+        }
+
+        #[derive(Deserialize)]
+        struct UpdatedIssue {
+            #[serde(rename = "updatedAt")]
+            updated_at: DateTime<Utc>,
+        }
+
+        let response: IssueUpdateResponse = self.execute_query(query, Some(variables)).await?;
+
+        match response.issue_update {
+            IssueUpdateMutation {
+                success: true,
+                issue: Some(issue),
+            } => {
+                debug!("Updated issue {}: {:?}", issue_id, update);
+                Ok(issue.updated_at)
+            }
+            _ => Err(Error::LinearApi {
+                message: format!("Failed to update issue {}", issue_id),
+            }),
+        }
+    }
+
+    /// Move an issue to the workflow state with the given name in the issue's team
+    pub async fn move_issue_to_state(&self, issue_id: &str, state_name: &str) -> Result<()> {
+        let query = r#"
+            query FindIssueState($issueId: String!, $name: String!) {
+                issue(id: $issueId) {
+                    team {
+                        states(filter: { name: { eqIgnoreCase: $name } }, first: 1) {
+                            nodes {
+                                id
+                                name
+                                type
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let mut variables = HashMap::new();
+        variables.insert("issueId", Value::String(issue_id.to_string()));
+        variables.insert("name", Value::String(state_name.to_string()));
+
+        #[derive(Deserialize)]
+        struct FindStateResponse {
+            issue: IssueTeamStates,
+        }
+
+        #[derive(Deserialize)]
+        struct IssueTeamStates {
+            team: TeamStates,
+        }
+
+        #[derive(Deserialize)]
+        struct TeamStates {
+            states: StatesConnection,
+        }
+
+        #[derive(Deserialize)]
+        struct StatesConnection {
+            nodes: Vec<WorkflowStateRaw>,
+        }
+
+        let response: FindStateResponse = self
+            .execute_query(query, Some(serde_json::to_value(variables)?))
+            .await?;
+
+        let state = response
+            .issue
+            .team
+            .states
+            .nodes
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::LinearApi {
+                message: format!("Workflow state '{}' not found for issue", state_name),
+            })?;
+
+        let query = r#"
+            mutation MoveIssueToState($issueId: String!, $stateId: String!) {
+                issueUpdate(id: $issueId, input: { stateId: $stateId }) {
+                    success
+                }
+            }
+        "#;
+
+        let mut variables = HashMap::new();
+        variables.insert("issueId", Value::String(issue_id.to_string()));
+        variables.insert("stateId", Value::String(state.id));
+
+        #[derive(Deserialize)]
+        struct IssueUpdateResponse {
+            #[serde(rename = "issueUpdate")]
+            issue_update: MutationResponse,
+        }
+
+        #[derive(Deserialize)]
+        struct MutationResponse {
+            success: bool,
+        }
+
+        let response: IssueUpdateResponse = self
+            .execute_query(query, Some(serde_json::to_value(variables)?))
+            .await?;
+
+        if !response.issue_update.success {
+            return Err(Error::LinearApi {
+                message: format!("Failed to move issue to state '{}'", state_name),
+            });
+        }
+
+        debug!("Moved issue {} to state '{}'", issue_id, state.name);
+        Ok(())
+    }
+
+    /// Post a comment on an issue, returning the new comment's ID
+    pub async fn create_comment(&self, issue_id: &str, body: &str) -> Result<String> {
+        let query = r#"
+            mutation CreateComment($issueId: String!, $body: String!) {
+                commentCreate(input: { issueId: $issueId, body: $body }) {
+                    success
+                    comment {
+                        id
+                    }
+                }
+            }
+        "#;
+
+        let mut variables = HashMap::new();
+        variables.insert("issueId", Value::String(issue_id.to_string()));
+        variables.insert("body", Value::String(body.to_string()));
+
+        #[derive(Deserialize)]
+        struct CreateCommentResponse {
+            #[serde(rename = "commentCreate")]
+            comment_create: CommentMutation,
+        }
+
+        #[derive(Deserialize)]
+        struct CommentMutation {
+            success: bool,
+            comment: Option<CommentId>,
+        }
+
+        #[derive(Deserialize)]
+        struct CommentId {
+            id: String,
+        }
+
+        let response: CreateCommentResponse = self
+            .execute_query(query, Some(serde_json::to_value(variables)?))
+            .await?;
+
+        match response.comment_create {
+            CommentMutation {
+                success: true,
+                comment: Some(comment),
+            } => {
+                debug!("Created comment {} on issue {}", comment.id, issue_id);
+                Ok(comment.id)
+            }
+            _ => Err(Error::LinearApi {
+                message: format!("Failed to create comment on issue {}", issue_id),
+            }),
+        }
+    }
+
+    /// Replace the body of an existing comment
+    pub async fn update_comment(&self, comment_id: &str, body: &str) -> Result<()> {
+        let query = r#"
+            mutation UpdateComment($commentId: String!, $body: String!) {
+                commentUpdate(id: $commentId, input: { body: $body }) {
+                    success
+                }
+            }
+        "#;
+
+        let mut variables = HashMap::new();
+        variables.insert("commentId", Value::String(comment_id.to_string()));
+        variables.insert("body", Value::String(body.to_string()));
+
+        #[derive(Deserialize)]
+        struct UpdateCommentResponse {
+            #[serde(rename = "commentUpdate")]
+            comment_update: MutationResponse,
+        }
+
+        #[derive(Deserialize)]
+        struct MutationResponse {
+            success: bool,
+        }
+
+        let response: UpdateCommentResponse = self
+            .execute_query(query, Some(serde_json::to_value(variables)?))
+            .await?;
+
+        if !response.comment_update.success {
+            return Err(Error::LinearApi {
+                message: format!("Failed to update comment {}", comment_id),
+            });
+        }
+
+        debug!("Updated comment {}", comment_id);
+        Ok(())
+    }
+
+    /// Create or update the link attachment with the given URL on an issue, returning its ID.
+    ///
+    /// Linear treats the URL as the attachment's identity, so posting the same URL again
+    /// updates the existing attachment instead of adding another one.
+    pub async fn upsert_attachment(
+        &self,
+        issue_id: &str,
+        url: &str,
+        title: &str,
+        subtitle: Option<&str>,
+    ) -> Result<String> {
+        let query = r#"
+            mutation UpsertAttachment($issueId: String!, $url: String!, $title: String!, $subtitle: String) {
+                attachmentCreate(input: { issueId: $issueId, url: $url, title: $title, subtitle: $subtitle }) {
+                    success
+                    attachment {
+                        id
+                    }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "issueId": issue_id,
+            "url": url,
+            "title": title,
+            "subtitle": subtitle,
+        });
+
+        #[derive(Deserialize)]
+        struct AttachmentResponse {
+            #[serde(rename = "attachmentCreate")]
+            attachment_create: AttachmentMutation,
+        }
+
+        #[derive(Deserialize)]
+        struct AttachmentMutation {
+            success: bool,
+            attachment: Option<AttachmentId>,
+        }
+
+        #[derive(Deserialize)]
+        struct AttachmentId {
+            id: String,
+        }
+
+        let response: AttachmentResponse = self.execute_query(query, Some(variables)).await?;
+
+        match response.attachment_create {
+            AttachmentMutation {
+                success: true,
+                attachment: Some(attachment),
+            } => {
+                debug!(
+                    "Upserted attachment {} on issue {}",
+                    attachment.id, issue_id
+                );
+                Ok(attachment.id)
+            }
+            _ => Err(Error::LinearApi {
+                message: format!("Failed to create attachment on issue {}", issue_id),
+            }),
+        }
+    }
+
     pub async fn get_or_create_label(&self, label_name: &str) -> Result<String> {
         // First try to find existing label
         if let Some(label_id) = self.find_label(label_name).await? {
@@ -1,2 +1,4 @@
 pub mod linear;
 pub mod motion;
+#[cfg(test)]
+pub(crate) mod testing;
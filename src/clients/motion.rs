@@ -9,7 +9,10 @@ use std::num::NonZeroU32;
 use std::sync::{Arc, Mutex};
 use tracing::{debug, error, info};
 
-#[derive(Debug, Clone)]
+/// Motion's default status for completed tasks
+pub const COMPLETED_STATUS: &str = "Completed";
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum TaskDuration {
     None,
     Reminder,
@@ -74,7 +77,6 @@ impl From<u32> for TaskDuration {
 #[derive(Debug)]
 pub struct MotionClient {
     client: ClientWithMiddleware,
-    api_key: String,
     base_url: String,
     rate_limiter: RateLimiter<
         governor::state::direct::NotKeyed,
@@ -98,7 +100,7 @@ pub struct Label {
     pub name: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct AutoScheduled {
     #[serde(rename = "startDate")]
     pub start_date: Option<DateTime<Utc>>,
@@ -154,6 +156,153 @@ pub struct MotionTask {
     pub custom_field_values: Option<serde_json::Value>,
 }
 
+/// Fields to change on a Motion task, unset fields are left alone
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct TaskUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(rename = "dueDate", skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<TaskDuration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    #[serde(rename = "autoScheduled", skip_serializing_if = "Option::is_none")]
+    pub auto_scheduled: Option<AutoScheduled>,
+    /// The API names of fields to clear, sent as `null`
+    #[serde(skip)]
+    pub cleared: Vec<String>,
+}
+
+impl TaskUpdate {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The API names of the fields that are set or cleared, e.g. "dueDate"
+    pub fn fields(&self) -> Vec<String> {
+        self.to_map().into_iter().map(|(field, _)| field).collect()
+    }
+
+    /// The JSON value of a set field, `null` for a cleared one
+    pub fn field_value(&self, field: &str) -> Option<serde_json::Value> {
+        self.to_map().remove(field)
+    }
+
+    /// The fields whose value differs from `base`, with fields `base` set and this leaves
+    /// unset cleared
+    pub fn changes_since(&self, base: &TaskUpdate) -> Result<TaskUpdate> {
+        let base = base.to_map();
+        let mut map = self.to_map();
+        for field in base.keys() {
+            map.entry(field.clone()).or_insert(serde_json::Value::Null);
+        }
+        map.retain(|field, value| base.get(field).unwrap_or(&serde_json::Value::Null) != value);
+        Self::from_map(map)
+    }
+
+    /// A copy with the given fields unset
+    pub fn without(&self, fields: &[String]) -> Result<TaskUpdate> {
+        Self::from_map(
+            self.to_map()
+                .into_iter()
+                .filter(|(field, _)| !fields.contains(field))
+                .collect(),
+        )
+    }
+
+    /// A copy with the given fields taken from `other`, unset where `other` leaves them unset
+    pub fn with_fields_from(&self, other: &TaskUpdate, fields: &[String]) -> Result<TaskUpdate> {
+        let mut map = self.to_map();
+        let other = other.to_map();
+        for field in fields {
+            match other.get(field) {
+                Some(value) => map.insert(field.clone(), value.clone()),
+                None => map.remove(field),
+            };
+        }
+        Self::from_map(map)
+    }
+
+    /// The PATCH body: set fields with their value, cleared fields as `null`
+    pub fn to_map(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut map = match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(map)) => map,
+            _ => serde_json::Map::new(),
+        };
+        for field in &self.cleared {
+            map.insert(field.clone(), serde_json::Value::Null);
+        }
+        map
+    }
+
+    fn from_map(map: serde_json::Map<String, serde_json::Value>) -> Result<Self> {
+        let (cleared, set): (serde_json::Map<_, _>, serde_json::Map<_, _>) =
+            map.into_iter().partition(|(_, value)| value.is_null());
+
+        let mut update: Self = serde_json::from_value(serde_json::Value::Object(set))?;
+        update.cleared = cleared.into_iter().map(|(field, _)| field).collect();
+        Ok(update)
+    }
+}
+
+impl MotionTask {
+    /// Whether the sync created this task, i.e. it carries the sync label
+    pub fn is_synced_task(&self) -> bool {
+        self.labels
+            .iter()
+            .flatten()
+            .any(|label| label.name == "linear-sync")
+    }
+
+    /// The Linear identifier this task was created for, from the `[IDENT]` name prefix
+    /// or, if the name was edited, the Linear link in the description
+    pub fn linear_identifier(&self) -> Option<String> {
+        let from_name = self
+            .name
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once(']'))
+            .map(|(identifier, _)| identifier);
+        let from_link = || {
+            let description = self.description.as_deref()?;
+            let (_, rest) = description.rsplit_once("/issue/")?;
+            rest.split(|c: char| c == '/' || c.is_whitespace()).next()
+        };
+
+        from_name
+            .or_else(from_link)
+            .filter(|identifier| !identifier.is_empty())
+            .map(str::to_string)
+    }
+
+    /// Why Motion can't get this task done by `deadline`, if it can't
+    pub fn at_risk_reason(&self, deadline: Option<DateTime<Utc>>) -> Option<String> {
+        if self.completed == Some(true) {
+            return None;
+        }
+
+        if self.scheduling_issue == Some(true) {
+            return Some("Motion could not schedule this task".to_string());
+        }
+
+        match (self.scheduled_end, deadline) {
+            (Some(end), Some(deadline)) if end > deadline => Some(format!(
+                "Scheduled to finish at {}, after the deadline of {}",
+                end.format("%Y-%m-%d %H:%M UTC"),
+                deadline.format("%Y-%m-%d %H:%M UTC")
+            )),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MotionWorkspace {
     pub id: String,
@@ -162,6 +311,8 @@ pub struct MotionWorkspace {
     pub team_id: Option<String>,
     #[serde(rename = "type")]
     pub workspace_type: String, // "team" or "individual"
+    #[serde(default)]
+    pub statuses: Option<Vec<Status>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -206,19 +357,10 @@ pub struct MotionLabel {
     pub color_hex: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-struct MotionResponse<T> {
-    #[serde(flatten)]
-    data: Option<T>,
-    meta: Option<MotionMeta>,
-}
-
 #[derive(Debug, Deserialize)]
 struct MotionMeta {
     #[serde(rename = "nextCursor")]
     next_cursor: Option<String>,
-    #[serde(rename = "pageSize")]
-    page_size: Option<i32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -230,13 +372,24 @@ struct TaskListResponse {
 #[derive(Debug, Deserialize)]
 struct WorkspaceListResponse {
     workspaces: Vec<MotionWorkspace>,
-    meta: Option<MotionMeta>,
 }
 
 #[derive(Debug, Deserialize)]
 struct LabelListResponse {
     labels: Vec<MotionLabel>,
-    meta: Option<MotionMeta>,
+}
+
+/// Percent-encode a value for use in a query string
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
 }
 
 impl MotionClient {
@@ -269,7 +422,6 @@ impl MotionClient {
 
         Ok(Self {
             client,
-            api_key,
             base_url: "https://api.usemotion.com/v1".to_string(),
             rate_limiter,
             cached_workspaces: Arc::new(Mutex::new(None)),
@@ -281,6 +433,17 @@ impl MotionClient {
         Ok(())
     }
 
+    /// 404s get their own error so callers can tell a deleted task from a failed request
+    fn error_for_status(status: reqwest::StatusCode, url: &str, text: String) -> Error {
+        if status == reqwest::StatusCode::NOT_FOUND {
+            Error::NotFound(url.to_string())
+        } else {
+            Error::MotionApi {
+                message: format!("HTTP {}: {}", status, text),
+            }
+        }
+    }
+
     async fn make_request<T: for<'de> Deserialize<'de>>(&self, endpoint: &str) -> Result<T> {
         self.rate_limit().await?;
 
@@ -300,9 +463,7 @@ impl MotionClient {
             let status = response.status();
             let text = response.text().await?;
             error!("Motion API error: {} - {}", status, text);
-            return Err(Error::MotionApi {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(Self::error_for_status(status, &url, text));
         }
 
         let text = response.text().await?;
@@ -343,9 +504,7 @@ impl MotionClient {
             let status = response.status();
             let text = response.text().await?;
             error!("Motion API error: {} - {}", status, text);
-            return Err(Error::MotionApi {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(Self::error_for_status(status, &url, text));
         }
 
         let text = response.text().await?;
@@ -390,9 +549,7 @@ impl MotionClient {
             let status = response.status();
             let text = response.text().await?;
             error!("Motion API error: {} - {}", status, text);
-            return Err(Error::MotionApi {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(Self::error_for_status(status, &url, text));
         }
 
         let response_data: T = response.json().await?;
@@ -449,6 +606,8 @@ impl MotionClient {
             #[serde(skip_serializing_if = "Option::is_none")]
             duration: Option<TaskDuration>,
             #[serde(skip_serializing_if = "Option::is_none")]
+            status: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
             labels: Option<Vec<String>>,
             // Auto-scheduling fields (sent as individual fields, not nested object)
             #[serde(rename = "autoScheduled", skip_serializing_if = "Option::is_none")]
@@ -477,6 +636,7 @@ impl MotionClient {
             priority: task.priority.clone(),
             due_date: task.due_date,
             duration: task.duration.clone(),
+            status: task.status.as_ref().map(|s| s.name.clone()),
             labels: task
                 .labels
                 .clone()
@@ -494,27 +654,7 @@ impl MotionClient {
     }
 
     pub async fn update_task(&self, task_id: &str, task: &MotionTask) -> Result<MotionTask> {
-        debug!("Updating Motion task: {}", task_id);
-
-        #[derive(Serialize)]
-        struct UpdateTaskRequest {
-            #[serde(skip_serializing_if = "Option::is_none")]
-            name: Option<String>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            description: Option<String>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            priority: Option<String>,
-            #[serde(rename = "dueDate", skip_serializing_if = "Option::is_none")]
-            due_date: Option<DateTime<Utc>>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            duration: Option<TaskDuration>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            status: Option<String>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            labels: Option<Vec<String>>,
-        }
-
-        let request = UpdateTaskRequest {
+        let update = TaskUpdate {
             name: Some(task.name.clone()),
             description: task.description.clone(),
             priority: task.priority.clone(),
@@ -525,10 +665,23 @@ impl MotionClient {
                 .labels
                 .clone()
                 .map(|l| l.into_iter().map(|l| l.name).collect()),
+            auto_scheduled: task.auto_scheduled.clone(),
+            ..Default::default()
         };
 
+        self.update_task_fields(task_id, &update).await
+    }
+
+    /// PATCH only the fields set in `update`
+    pub async fn update_task_fields(
+        &self,
+        task_id: &str,
+        update: &TaskUpdate,
+    ) -> Result<MotionTask> {
+        debug!("Updating Motion task: {}", task_id);
+
         let endpoint = format!("tasks/{}", task_id);
-        let updated_task: MotionTask = self.make_patch_request(&endpoint, &request).await?;
+        let updated_task: MotionTask = self.make_patch_request(&endpoint, &update.to_map()).await?;
         debug!("Updated Motion task: {}", task_id);
         Ok(updated_task)
     }
@@ -539,11 +692,32 @@ impl MotionClient {
         Ok(task)
     }
 
+    /// Fetch every page of a task listing by following `meta.nextCursor`
+    async fn list_task_pages(&self, endpoint: &str) -> Result<Vec<MotionTask>> {
+        let mut tasks = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let page_endpoint = match &cursor {
+                Some(cursor) => format!("{}&cursor={}", endpoint, encode_query_value(cursor)),
+                None => endpoint.to_string(),
+            };
+            let response: TaskListResponse = self.make_request(&page_endpoint).await?;
+            tasks.extend(response.tasks);
+
+            match response.meta.next_cursor {
+                Some(next) if !next.is_empty() => cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        Ok(tasks)
+    }
+
     pub async fn list_tasks(&self, workspace_id: &str) -> Result<Vec<MotionTask>> {
         let endpoint = format!("tasks?workspaceId={}", workspace_id);
-        let response: TaskListResponse = self.make_request(&endpoint).await?;
-        Ok(response
-            .tasks
+        let tasks = self.list_task_pages(&endpoint).await?;
+        Ok(tasks
             .into_iter()
             .filter(|t| !t.completed.unwrap_or(false))
             .collect())
@@ -551,10 +725,9 @@ impl MotionClient {
 
     pub async fn list_completed_tasks(&self, workspace_id: &str) -> Result<Vec<MotionTask>> {
         let endpoint = format!("tasks?workspaceId={}&includeAllStatuses=true", workspace_id);
-        let response: TaskListResponse = self.make_request(&endpoint).await?;
+        let tasks = self.list_task_pages(&endpoint).await?;
 
-        let completed_tasks: Vec<MotionTask> = response
-            .tasks
+        let completed_tasks: Vec<MotionTask> = tasks
             .into_iter()
             .filter(|task| task.completed.unwrap_or(false))
             .collect();
@@ -570,18 +743,25 @@ impl MotionClient {
     pub async fn mark_task_completed(&self, task_id: &str) -> Result<MotionTask> {
         debug!("Marking Motion task as completed: {}", task_id);
 
+        let updated_task = self.set_task_status(task_id, COMPLETED_STATUS).await?;
+        info!("Marked Motion task as completed: {}", task_id);
+        Ok(updated_task)
+    }
+
+    pub async fn set_task_status(&self, task_id: &str, status: &str) -> Result<MotionTask> {
+        debug!("Setting Motion task {} status to '{}'", task_id, status);
+
         #[derive(Serialize)]
-        struct CompleteTaskRequest {
+        struct TaskStatusRequest {
             status: String,
         }
 
-        let request = CompleteTaskRequest {
-            status: "Completed".to_string(), // Motion's default completed status name
+        let request = TaskStatusRequest {
+            status: status.to_string(),
         };
 
         let endpoint = format!("tasks/{}", task_id);
         let updated_task: MotionTask = self.make_patch_request(&endpoint, &request).await?;
-        info!("Marked Motion task as completed: {}", task_id);
         Ok(updated_task)
     }
 
@@ -604,9 +784,7 @@ impl MotionClient {
             let status = response.status();
             let text = response.text().await?;
             error!("Motion API error: {} - {}", status, text);
-            return Err(Error::MotionApi {
-                message: format!("HTTP {}: {}", status, text),
-            });
+            return Err(Self::error_for_status(status, &url, text));
         }
 
         info!("Successfully deleted Motion task: {}", task_id);
@@ -697,7 +875,123 @@ mod test {
         let jd = &mut serde_json::Deserializer::from_str(x);
 
         // deserialize with serde_json
-        let task: MotionTask = serde_path_to_error::deserialize(jd).unwrap();
+        let _task: MotionTask = serde_path_to_error::deserialize(jd).unwrap();
+    }
+
+    #[test]
+    fn test_task_update_diff() {
+        let base = TaskUpdate {
+            name: Some("[CAR-1] Ship it".to_string()),
+            priority: Some("HIGH".to_string()),
+            duration: Some(TaskDuration::Minutes(60)),
+            ..Default::default()
+        };
+        let projection = TaskUpdate {
+            priority: Some("ASAP".to_string()),
+            status: Some("Todo".to_string()),
+            ..base.clone()
+        };
+
+        let changes = projection.changes_since(&base).unwrap();
+        assert_eq!(changes.fields(), vec!["priority", "status"]);
+        assert_eq!(changes.priority.as_deref(), Some("ASAP"));
+        assert_eq!(changes.name, None);
+
+        let kept = changes.without(&["priority".to_string()]).unwrap();
+        assert_eq!(kept.fields(), vec!["status"]);
+        assert!(kept.without(&kept.fields()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_task_update_clears_unset_fields() {
+        let base = TaskUpdate {
+            name: Some("[CAR-1] Ship it".to_string()),
+            due_date: Some(Utc::now()),
+            ..Default::default()
+        };
+        let projection = TaskUpdate {
+            due_date: None,
+            ..base.clone()
+        };
+
+        let changes = projection.changes_since(&base).unwrap();
+        assert_eq!(changes.cleared, vec!["dueDate"]);
+        assert_eq!(changes.fields(), vec!["dueDate"]);
+        assert_eq!(
+            serde_json::to_value(changes.to_map()).unwrap(),
+            serde_json::json!({ "dueDate": null })
+        );
+
+        let kept = changes.without(&["dueDate".to_string()]).unwrap();
+        assert!(kept.is_empty());
+        assert!(projection.changes_since(&projection).unwrap().is_empty());
+
+        // Malformed values are reported instead of dropping the whole update
+        let mut map = base.to_map();
+        map.insert("priority".to_string(), serde_json::json!(3));
+        assert!(TaskUpdate::from_map(map).is_err());
+    }
+
+    #[test]
+    fn test_not_found_is_its_own_error() {
+        let url = "https://api.usemotion.com/v1/tasks/tk_1";
+        assert!(matches!(
+            MotionClient::error_for_status(reqwest::StatusCode::NOT_FOUND, url, String::new()),
+            Error::NotFound(found) if found == url
+        ));
+        assert!(matches!(
+            MotionClient::error_for_status(
+                reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+                url,
+                "boom".to_string()
+            ),
+            Error::MotionApi { message } if message.contains("boom")
+        ));
+    }
+
+    #[test]
+    fn test_linear_identifier() {
+        let mut task = MotionTask {
+            name: "[CAR-126] Reach out to dentist".to_string(),
+            description: Some("Linear: https://linear.app/personal/issue/CAR-126".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(task.linear_identifier().as_deref(), Some("CAR-126"));
+
+        task.name = "Dentist".to_string();
+        assert_eq!(task.linear_identifier().as_deref(), Some("CAR-126"));
+
+        task.description = None;
+        assert_eq!(task.linear_identifier(), None);
+
+        assert_eq!(encode_query_value("a+b/c="), "a%2Bb%2Fc%3D");
+    }
+
+    #[test]
+    fn test_at_risk_reason() {
+        let mut task: MotionTask = serde_json::from_value(serde_json::json!({
+            "name": "[CAR-1] Ship it",
+            "scheduledEnd": "2025-03-04T16:00:00Z",
+            "schedulingIssue": false,
+        }))
+        .unwrap();
+        let deadline = |s: &str| Some(s.parse::<DateTime<Utc>>().unwrap());
+
+        assert_eq!(task.at_risk_reason(deadline("2025-03-04T17:00:00Z")), None);
+        assert_eq!(task.at_risk_reason(None), None);
+        assert!(task
+            .at_risk_reason(deadline("2025-03-04T12:00:00Z"))
+            .unwrap()
+            .starts_with("Scheduled to finish"));
+
+        task.scheduling_issue = Some(true);
+        assert_eq!(
+            task.at_risk_reason(None).as_deref(),
+            Some("Motion could not schedule this task")
+        );
+
+        task.completed = Some(true);
+        assert_eq!(task.at_risk_reason(None), None);
     }
 
     #[test]
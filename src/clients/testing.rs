@@ -0,0 +1,26 @@
+//! Fixtures shared by the unit tests
+
+use crate::clients::{linear::LinearIssue, motion::MotionTask};
+
+/// An unstarted issue in the "CAR" team, without an estimate, cycle or due date
+pub fn issue(id: &str, identifier: &str, title: &str) -> LinearIssue {
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "identifier": identifier,
+        "title": title,
+        "state": { "id": "state-1", "name": "Todo", "state_type": "unstarted" },
+        "team": { "id": "team-1", "name": "Personal", "key": "CAR" },
+        "created_at": "2025-01-01T00:00:00Z",
+        "updated_at": "2025-01-01T00:00:00Z",
+        "labels": [],
+    }))
+    .unwrap()
+}
+
+pub fn task(id: &str, name: &str) -> MotionTask {
+    MotionTask {
+        id: Some(id.to_string()),
+        name: name.to_string(),
+        ..Default::default()
+    }
+}
@@ -2,4 +2,9 @@ pub mod loader;
 pub mod models;
 
 pub use loader::ConfigLoader;
-pub use models::{AppConfig, ScheduleOverride, SyncRules, SyncSource, TimeEstimateStrategy};
+pub use models::{
+    AppConfig, CompletionAction, ConflictPolicy, CycleRules, DeadlineInputs, DefaultDeadlinePolicy,
+    DeletedTaskPolicy, DeletionGuard, DriftCheck, DriftMode, LabelPriorityOverride, PriorityMap,
+    ReverseSyncRules, ScheduleFeedback, ScheduleFeedbackMode, ScheduleOverride, StatusMap,
+    SyncRules, SyncSource, TerminalAction, TerminalStateRules, TimeEstimateStrategy,
+};
@@ -1,5 +1,10 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
 
 use super::ConfigLoader;
 
@@ -11,6 +16,12 @@ pub struct AppConfig {
     pub database_path: Option<String>,
     pub polling_interval_seconds: u64,
     pub schedule_overrides: Option<Vec<ScheduleOverride>>,
+    /// IANA timezone used for dates and schedule overrides, defaults to the system zone
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Days a Motion task deleted by the sync can still be restored
+    #[serde(default = "AppConfig::default_tombstone_retention_days")]
+    pub tombstone_retention_days: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,17 +38,285 @@ pub struct SyncRules {
     pub default_task_duration_mins: u32,
     pub completed_linear_tag: String,
     pub time_estimate_strategy: TimeEstimateStrategy,
+    #[serde(default)]
+    pub priority_map: PriorityMap,
+    /// Local time of day (HH:MM) a Linear due date becomes the Motion deadline
+    #[serde(default = "SyncRules::default_due_time")]
+    pub due_time: String,
+    /// Deadline policies for issues without a due date, the first that applies wins
+    #[serde(default = "SyncRules::default_deadline_policies")]
+    pub default_deadline: Vec<DefaultDeadlinePolicy>,
+    #[serde(default)]
+    pub cycles: CycleRules,
+    #[serde(default)]
+    pub status_map: StatusMap,
+    #[serde(default)]
+    pub terminal_states: TerminalStateRules,
+    #[serde(default)]
+    pub completion_action: CompletionAction,
+    #[serde(default)]
+    pub schedule_feedback: ScheduleFeedback,
+    /// Label added to Linear issues Motion can't finish by their deadline, removed once it can
+    #[serde(default)]
+    pub at_risk_label: Option<String>,
+    #[serde(default)]
+    pub reverse_sync: ReverseSyncRules,
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
+    #[serde(default)]
+    pub deleted_task_policy: DeletedTaskPolicy,
+    /// Label added to the Linear issue under the `label` deleted task policy
+    #[serde(default = "SyncRules::default_deleted_task_label")]
+    pub deleted_task_label: String,
+    #[serde(default)]
+    pub drift_check: DriftCheck,
+    #[serde(default)]
+    pub deletion_guard: DeletionGuard,
+}
+
+/// Limit on how many Motion tasks a single cleanup may delete, above it deletions are held
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeletionGuard {
+    Off,
+    /// Hold deletions when more than this many tasks would be deleted
+    Count(u32),
+    /// Hold deletions when more than this percentage of the source's tasks would be deleted,
+    /// once more than `DeletionGuard::PERCENT_MIN_DELETIONS` would be
+    Percent(f64),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftMode {
+    Off,
+    /// Log differences between Motion and the stored state
+    #[default]
+    Report,
+    /// Log differences and write the expected values back to Motion
+    Repair,
+}
+
+/// Periodic comparison of every mapped Motion task against the stored Linear state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftCheck {
+    #[serde(default)]
+    pub mode: DriftMode,
+    #[serde(default = "DriftCheck::default_interval_hours")]
+    pub interval_hours: u32,
+}
+
+/// What to do when a synced task has been deleted in Motion
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DeletedTaskPolicy {
+    /// Create a new Motion task for the issue
+    Recreate,
+    /// Stop syncing the issue
+    #[default]
+    Ignore,
+    /// Stop syncing the issue and add `deleted_task_label` to it in Linear
+    Label,
+}
+
+/// What to do when a field changed in Linear was also edited in Motion since the last sync
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Overwrite the Motion edit
+    LinearWins,
+    /// Keep the Motion edit
+    KeepMotion,
+    /// Keep the Motion edit and record the conflict on the mapping
+    #[default]
+    Report,
+}
+
+/// Fields whose edits in Motion are pushed back to Linear. Linear wins when both changed.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ReverseSyncRules {
+    /// Motion duration to Linear estimate, on the team's estimation scale
+    #[serde(default)]
+    pub duration: bool,
+    #[serde(default)]
+    pub priority: bool,
+    /// Motion deadline to Linear due date, in the configured timezone
+    #[serde(default)]
+    pub due_date: bool,
+}
+
+impl ReverseSyncRules {
+    pub fn any(&self) -> bool {
+        self.duration || self.priority || self.due_date
+    }
+}
+
+/// How Motion's planned work blocks are shown on the Linear issue
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleFeedbackMode {
+    #[default]
+    Off,
+    /// Keep a single comment on the issue up to date
+    Comment,
+    /// Keep a single link attachment on the issue up to date
+    Attachment,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleFeedback {
+    #[serde(default)]
+    pub mode: ScheduleFeedbackMode,
+    /// Link used for the attachment, `{task_id}` is replaced with the Motion task ID
+    #[serde(default = "ScheduleFeedback::default_attachment_url")]
+    pub attachment_url: String,
+}
+
+/// What to do to the Linear issue when its Motion task is completed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionAction {
+    /// Add `completed_linear_tag` to the issue
+    #[serde(default = "default_true")]
+    pub add_label: bool,
+    /// Move the issue to the workflow state with this name (e.g. "In Review")
+    #[serde(default)]
+    pub move_to_state: Option<String>,
+    /// Post this text as a comment on the issue
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// What to do with the Motion task when its Linear issue reaches a terminal state
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TerminalAction {
+    Delete,
+    Complete,
+    /// Move the task to `archive_status`
+    Archive,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalStateRules {
+    #[serde(default = "TerminalStateRules::default_completed")]
+    pub completed: TerminalAction,
+    #[serde(default = "TerminalStateRules::default_canceled")]
+    pub canceled: TerminalAction,
+    #[serde(default = "TerminalStateRules::default_duplicate")]
+    pub duplicate: TerminalAction,
+    #[serde(default = "TerminalStateRules::default_archive_status")]
+    pub archive_status: String,
+}
+
+/// Linear workflow state types
+pub const LINEAR_STATE_TYPES: [&str; 6] = [
+    "triage",
+    "backlog",
+    "unstarted",
+    "started",
+    "completed",
+    "canceled",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct StatusMap {
+    /// Linear state type (e.g. "started") to Motion status name
+    #[serde(default)]
+    pub by_type: HashMap<String, String>,
+    /// Linear state name (e.g. "In Review") to Motion status name, takes precedence over `by_type`
+    #[serde(default)]
+    pub by_name: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleRules {
+    /// Don't start scheduling an issue before its cycle starts
+    #[serde(default = "default_true")]
+    pub start_on_cycle_start: bool,
+    /// Don't create Motion tasks for issues in future cycles until the cycle starts
+    #[serde(default)]
+    pub hold_until_cycle_start: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DefaultDeadlinePolicy {
+    /// Leave the deadline unset
+    None,
+    /// A number of working days (Mon-Fri) after the issue was first synced
+    WorkingDays(u32),
+    /// The end of the issue's Linear cycle
+    CycleEnd,
+    /// The target date of the issue's Linear project
+    ProjectTargetDate,
+    /// Working days after the issue was first synced, by Linear priority (0-4)
+    PerPriority(BTreeMap<u32, u32>),
+}
+
+/// The issue data a default deadline policy is computed from
+#[derive(Debug, Clone, Default)]
+pub struct DeadlineInputs<'a> {
+    /// When the issue was first synced
+    pub anchor: DateTime<Utc>,
+    pub priority: Option<u32>,
+    pub cycle_ends_at: Option<DateTime<Utc>>,
+    pub project_target_date: Option<&'a str>,
+}
+
+/// Motion priorities accepted by the API
+pub const MOTION_PRIORITIES: [&str; 4] = ["ASAP", "HIGH", "MEDIUM", "LOW"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriorityMap {
+    /// Linear priority (0 = no priority, 1 = urgent .. 4 = low) to Motion priority
+    #[serde(default = "PriorityMap::default_levels")]
+    pub levels: BTreeMap<u32, String>,
+    /// Checked in order before `levels`, the first matching label wins
+    #[serde(default)]
+    pub label_overrides: Vec<LabelPriorityOverride>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelPriorityOverride {
+    pub label: String,
+    pub priority: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeEstimateStrategy {
     pub fibonacci: Option<HashMap<String, u32>>,
+    #[serde(default)]
+    pub exponential: Option<HashMap<String, u32>>,
     pub tshirt: Option<HashMap<String, u32>>,
     pub linear: Option<HashMap<String, u32>>,
     pub points: Option<HashMap<String, u32>>,
     pub default_duration_mins: Option<u32>,
 }
 
+/// A Linear team's estimation settings (`issueEstimationType` and friends)
+#[derive(Debug, Clone, PartialEq)]
+pub struct EstimationScale {
+    /// "notUsed", "exponential", "fibonacci", "linear" or "tShirt"
+    pub kind: String,
+    pub extended: bool,
+    pub allow_zero: bool,
+}
+
+/// Linear stores t-shirt sizes using the fibonacci values
+const TSHIRT_SIZES: [(u32, &str); 7] = [
+    (1, "XS"),
+    (2, "S"),
+    (3, "M"),
+    (5, "L"),
+    (8, "XL"),
+    (13, "XXL"),
+    (21, "XXXL"),
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScheduleOverride {
     pub name: String,
@@ -47,6 +326,32 @@ pub struct ScheduleOverride {
     pub days: Vec<String>,  // mon, tue, wed, thu, fri, sat, sun
 }
 
+impl ScheduleOverride {
+    /// Whether the override applies at the given instant in the given timezone
+    pub fn is_active_at(&self, now: DateTime<Utc>, timezone: Tz) -> bool {
+        let local = now.with_timezone(&timezone);
+        let day = local.weekday().to_string().to_lowercase();
+        if !self.days.contains(&day) {
+            return false;
+        }
+
+        let (Ok(start), Ok(end)) = (
+            NaiveTime::parse_from_str(&self.start_time, "%H:%M"),
+            NaiveTime::parse_from_str(&self.end_time, "%H:%M"),
+        ) else {
+            return false;
+        };
+
+        let time = local.time();
+        if start <= end {
+            start <= time && time < end
+        } else {
+            // Overnight window, e.g. 22:00-06:00
+            time >= start || time < end
+        }
+    }
+}
+
 impl SyncSource {
     pub fn effective_sync_rules(&self, global_rules: &SyncRules) -> SyncRules {
         match &self.sync_rules {
@@ -56,19 +361,380 @@ impl SyncSource {
     }
 }
 
+impl Default for CycleRules {
+    fn default() -> Self {
+        Self {
+            start_on_cycle_start: true,
+            hold_until_cycle_start: false,
+        }
+    }
+}
+
+impl Default for DeletionGuard {
+    fn default() -> Self {
+        Self::Percent(50.0)
+    }
+}
+
+impl DeletionGuard {
+    /// Deletions a percentage guard always lets through, so sources with few tasks aren't held
+    pub const PERCENT_MIN_DELETIONS: usize = 3;
+
+    /// Whether deleting `deletions` of the source's `total` synced tasks may go ahead
+    pub fn allows(&self, deletions: usize, total: usize) -> bool {
+        match *self {
+            Self::Off => true,
+            Self::Count(max) => deletions <= max as usize,
+            Self::Percent(max) => {
+                deletions <= Self::PERCENT_MIN_DELETIONS
+                    || deletions as f64 * 100.0 / total as f64 <= max
+            }
+        }
+    }
+}
+
+impl Default for DriftCheck {
+    fn default() -> Self {
+        Self {
+            mode: DriftMode::default(),
+            interval_hours: Self::default_interval_hours(),
+        }
+    }
+}
+
+impl DriftCheck {
+    fn default_interval_hours() -> u32 {
+        24
+    }
+}
+
+impl Default for ScheduleFeedback {
+    fn default() -> Self {
+        Self {
+            mode: ScheduleFeedbackMode::default(),
+            attachment_url: Self::default_attachment_url(),
+        }
+    }
+}
+
+impl ScheduleFeedback {
+    fn default_attachment_url() -> String {
+        "https://app.usemotion.com/?task={task_id}".to_string()
+    }
+
+    /// The attachment link for a Motion task
+    pub fn attachment_url_for(&self, task_id: &str) -> String {
+        self.attachment_url.replace("{task_id}", task_id)
+    }
+}
+
+impl Default for CompletionAction {
+    fn default() -> Self {
+        Self {
+            add_label: true,
+            move_to_state: None,
+            comment: None,
+        }
+    }
+}
+
+impl Default for TerminalStateRules {
+    fn default() -> Self {
+        Self {
+            completed: Self::default_completed(),
+            canceled: Self::default_canceled(),
+            duplicate: Self::default_duplicate(),
+            archive_status: Self::default_archive_status(),
+        }
+    }
+}
+
+impl TerminalStateRules {
+    fn default_completed() -> TerminalAction {
+        TerminalAction::Complete
+    }
+
+    fn default_canceled() -> TerminalAction {
+        TerminalAction::Complete
+    }
+
+    fn default_duplicate() -> TerminalAction {
+        TerminalAction::Delete
+    }
+
+    fn default_archive_status() -> String {
+        "Canceled".to_string()
+    }
+}
+
+impl SyncRules {
+    fn default_deleted_task_label() -> String {
+        "deleted-in-motion".to_string()
+    }
+
+    fn default_due_time() -> String {
+        "23:59".to_string()
+    }
+
+    fn default_deadline_policies() -> Vec<DefaultDeadlinePolicy> {
+        vec![
+            DefaultDeadlinePolicy::CycleEnd,
+            DefaultDeadlinePolicy::WorkingDays(1),
+        ]
+    }
+
+    /// Convert a Linear due date (YYYY-MM-DD) to the Motion deadline in the given timezone
+    pub fn deadline_for_due_date(&self, due_date: &str, timezone: Tz) -> Option<DateTime<Utc>> {
+        let date = NaiveDate::parse_from_str(due_date, "%Y-%m-%d").ok()?;
+        self.deadline_for_date(date, timezone)
+    }
+
+    /// The deadline for an issue without a due date, from the first policy that applies.
+    ///
+    /// `None` always applies, so it leaves the deadline unset and ends the search.
+    pub fn default_deadline(&self, inputs: &DeadlineInputs, timezone: Tz) -> Option<DateTime<Utc>> {
+        for policy in &self.default_deadline {
+            let deadline = match policy {
+                DefaultDeadlinePolicy::None => return None,
+                DefaultDeadlinePolicy::WorkingDays(days) => {
+                    self.working_days_after(inputs.anchor, *days, timezone)
+                }
+                DefaultDeadlinePolicy::CycleEnd => inputs
+                    .cycle_ends_at
+                    .and_then(|ends_at| self.deadline_for_cycle_end(ends_at, timezone)),
+                DefaultDeadlinePolicy::ProjectTargetDate => inputs
+                    .project_target_date
+                    .and_then(|date| self.deadline_for_due_date(date, timezone)),
+                DefaultDeadlinePolicy::PerPriority(working_days) => inputs
+                    .priority
+                    .and_then(|p| working_days.get(&p))
+                    .or_else(|| working_days.get(&0))
+                    .and_then(|days| self.working_days_after(inputs.anchor, *days, timezone)),
+            };
+            if deadline.is_some() {
+                return deadline;
+            }
+        }
+
+        None
+    }
+
+    /// The due time on the last day of a cycle ending at `ends_at`
+    fn deadline_for_cycle_end(
+        &self,
+        ends_at: DateTime<Utc>,
+        timezone: Tz,
+    ) -> Option<DateTime<Utc>> {
+        let date = ends_at.with_timezone(&timezone).date_naive();
+        match self.deadline_for_date(date, timezone)? {
+            deadline if deadline <= ends_at => Some(deadline),
+            // Cycles usually end at midnight, so the last day is the one before
+            _ => self.deadline_for_date(date.pred_opt()?, timezone),
+        }
+    }
+
+    fn working_days_after(
+        &self,
+        anchor: DateTime<Utc>,
+        days: u32,
+        timezone: Tz,
+    ) -> Option<DateTime<Utc>> {
+        let mut date = anchor.with_timezone(&timezone).date_naive();
+        let mut remaining = days;
+
+        while remaining > 0 {
+            date = date.succ_opt()?;
+            if date.weekday().num_days_from_monday() < 5 {
+                remaining -= 1;
+            }
+        }
+
+        self.deadline_for_date(date, timezone)
+    }
+
+    fn deadline_for_date(&self, date: NaiveDate, timezone: Tz) -> Option<DateTime<Utc>> {
+        let time = NaiveTime::parse_from_str(&self.due_time, "%H:%M").ok()?;
+        let local = date.and_time(time);
+
+        // Times skipped by a DST change resolve to the following hour
+        timezone
+            .from_local_datetime(&local)
+            .earliest()
+            .or_else(|| {
+                timezone
+                    .from_local_datetime(&(local + chrono::Duration::hours(1)))
+                    .earliest()
+            })
+            .map(|deadline| deadline.with_timezone(&Utc))
+    }
+}
+
+impl StatusMap {
+    /// The Motion status for a Linear workflow state, if one is mapped
+    pub fn resolve(&self, state_name: &str, state_type: &str) -> Option<&str> {
+        self.by_name
+            .get(state_name)
+            .or_else(|| self.by_type.get(state_type))
+            .map(String::as_str)
+    }
+
+    /// All Motion statuses referenced by the map
+    pub fn motion_statuses(&self) -> impl Iterator<Item = &str> {
+        self.by_name
+            .values()
+            .chain(self.by_type.values())
+            .map(String::as_str)
+    }
+}
+
+impl Default for PriorityMap {
+    fn default() -> Self {
+        Self {
+            levels: Self::default_levels(),
+            label_overrides: Vec::new(),
+        }
+    }
+}
+
+impl PriorityMap {
+    fn default_levels() -> BTreeMap<u32, String> {
+        BTreeMap::from([
+            (0, "MEDIUM".to_string()),
+            (1, "ASAP".to_string()),
+            (2, "HIGH".to_string()),
+            (3, "MEDIUM".to_string()),
+            (4, "LOW".to_string()),
+        ])
+    }
+
+    /// The Linear priority that maps to a Motion priority, preferring an explicit level
+    /// over "No priority" when several levels share it
+    pub fn linear_priority(&self, motion_priority: &str) -> Option<u32> {
+        let mut levels = self
+            .levels
+            .iter()
+            .filter(|(_, priority)| priority.as_str() == motion_priority)
+            .map(|(level, _)| *level);
+
+        let first = levels.next()?;
+        Some(if first == 0 {
+            levels.next().unwrap_or(0)
+        } else {
+            first
+        })
+    }
+
+    /// Resolve the Motion priority for a Linear priority and its label names.
+    ///
+    /// A missing or unmapped priority is treated as Linear's 0 ("No priority").
+    pub fn resolve<'a>(
+        &self,
+        priority: Option<u32>,
+        labels: impl IntoIterator<Item = &'a str>,
+    ) -> String {
+        let labels: Vec<&str> = labels.into_iter().collect();
+
+        if let Some(label_override) = self
+            .label_overrides
+            .iter()
+            .find(|o| labels.contains(&o.label.as_str()))
+        {
+            return label_override.priority.clone();
+        }
+
+        priority
+            .and_then(|p| self.levels.get(&p))
+            .or_else(|| self.levels.get(&0))
+            .cloned()
+            .unwrap_or_else(|| "MEDIUM".to_string())
+    }
+}
+
+impl EstimationScale {
+    /// The estimate values Linear offers for this scale
+    pub fn values(&self) -> Vec<u32> {
+        let (base, extended): (&[u32], &[u32]) = match self.kind.to_lowercase().as_str() {
+            "exponential" => (&[1, 2, 4, 8, 16], &[32, 64]),
+            "fibonacci" | "tshirt" => (&[1, 2, 3, 5, 8], &[13, 21]),
+            "linear" => (&[1, 2, 3, 4, 5], &[6, 7]),
+            _ => (&[], &[]),
+        };
+
+        let mut values = Vec::new();
+        if self.allow_zero && !base.is_empty() {
+            values.push(0);
+        }
+        values.extend_from_slice(base);
+        if self.extended {
+            values.extend_from_slice(extended);
+        }
+        values
+    }
+
+    pub fn contains(&self, estimate: f64) -> bool {
+        estimate >= 0.0 && estimate.fract() == 0.0 && self.values().contains(&(estimate as u32))
+    }
+}
+
+/// The t-shirt size Linear displays for a numeric estimate
+pub fn tshirt_size(estimate: f64) -> Option<&'static str> {
+    TSHIRT_SIZES
+        .iter()
+        .find(|(value, _)| *value as f64 == estimate)
+        .map(|(_, size)| *size)
+}
+
 impl TimeEstimateStrategy {
     pub fn convert_estimate(&self, estimate: f64, estimate_type: &str) -> Option<u32> {
         let estimate_key = estimate.to_string();
 
         match estimate_type.to_lowercase().as_str() {
             "fibonacci" => self.fibonacci.as_ref()?.get(&estimate_key).copied(),
-            "tshirt" | "t-shirt" => self.tshirt.as_ref()?.get(&estimate_key).copied(),
+            "exponential" => self.exponential.as_ref()?.get(&estimate_key).copied(),
+            "tshirt" | "t-shirt" => {
+                let mappings = self.tshirt.as_ref()?;
+                tshirt_size(estimate)
+                    .and_then(|size| mappings.get(size))
+                    .or_else(|| mappings.get(&estimate_key))
+                    .copied()
+            }
             "linear" => self.linear.as_ref()?.get(&estimate_key).copied(),
             "points" => self.points.as_ref()?.get(&estimate_key).copied(),
             _ => None,
         }
     }
 
+    /// Convert an estimate using the mapping for the team's own estimation scale.
+    ///
+    /// Estimates that are not on the scale (e.g. zero when the team doesn't
+    /// allow it) or on a scale without a configured mapping fall back to the
+    /// default duration.
+    pub fn convert_estimate_for_scale(
+        &self,
+        estimate: f64,
+        scale: &EstimationScale,
+    ) -> Option<u32> {
+        if !scale.contains(estimate) {
+            return self.default_duration_mins;
+        }
+
+        self.convert_estimate(estimate, &scale.kind)
+            .or(self.default_duration_mins)
+    }
+
+    /// The estimate on the team's scale whose mapped duration is closest to `minutes`
+    pub fn estimate_for_duration(&self, minutes: u32, scale: &EstimationScale) -> Option<f64> {
+        scale
+            .values()
+            .into_iter()
+            .filter_map(|value| {
+                let duration = self.convert_estimate(value as f64, &scale.kind)?;
+                Some((duration.abs_diff(minutes), value))
+            })
+            .min()
+            .map(|(_, value)| value as f64)
+    }
+
     pub fn convert_estimate_by_value(&self, estimate: f64) -> Option<u32> {
         let estimate_key = estimate.to_string();
 
@@ -103,6 +769,10 @@ impl TimeEstimateStrategy {
 }
 
 impl AppConfig {
+    fn default_tombstone_retention_days() -> u32 {
+        30
+    }
+
     pub fn validate(&self) -> crate::Result<()> {
         use crate::Error;
 
@@ -145,6 +815,25 @@ impl AppConfig {
                     idx
                 )));
             }
+
+            if let Some(rules) = &source.sync_rules {
+                Self::validate_sync_rules(
+                    &format!("sync source {} ({})", idx, source.name),
+                    rules,
+                )?;
+            }
+        }
+
+        Self::validate_sync_rules("global sync rules", &self.global_sync_rules)?;
+
+        // Validate timezone
+        if let Some(timezone) = &self.timezone {
+            if timezone.parse::<Tz>().is_err() {
+                return Err(Error::Validation(format!(
+                    "Invalid timezone '{}': expected an IANA name such as 'America/New_York'",
+                    timezone
+                )));
+            }
         }
 
         // Validate database path
@@ -200,9 +889,143 @@ impl AppConfig {
         Ok(())
     }
 
+    /// The configured timezone, falling back to the system timezone and then UTC
+    pub fn timezone(&self) -> Tz {
+        let name = self
+            .timezone
+            .clone()
+            .or_else(|| iana_time_zone::get_timezone().ok());
+
+        match name.as_deref().map(str::parse::<Tz>) {
+            Some(Ok(tz)) => tz,
+            Some(Err(_)) | None => {
+                tracing::warn!("Could not determine timezone {:?}, using UTC", name);
+                Tz::UTC
+            }
+        }
+    }
+
+    fn validate_sync_rules(context: &str, rules: &SyncRules) -> crate::Result<()> {
+        use crate::Error;
+
+        if NaiveTime::parse_from_str(&rules.due_time, "%H:%M").is_err() {
+            return Err(Error::Validation(format!(
+                "Invalid due_time '{}' for {}: expected HH:MM",
+                rules.due_time, context
+            )));
+        }
+
+        for (level, priority) in &rules.priority_map.levels {
+            if *level > 4 {
+                return Err(Error::Validation(format!(
+                    "Invalid Linear priority {} in priority_map for {}: expected 0-4",
+                    level, context
+                )));
+            }
+
+            if !MOTION_PRIORITIES.contains(&priority.as_str()) {
+                return Err(Error::Validation(format!(
+                    "Invalid Motion priority '{}' in priority_map for {}. Valid priorities: {}",
+                    priority,
+                    context,
+                    MOTION_PRIORITIES.join(", ")
+                )));
+            }
+        }
+
+        for state_type in rules.status_map.by_type.keys() {
+            if !LINEAR_STATE_TYPES.contains(&state_type.as_str()) {
+                return Err(Error::Validation(format!(
+                    "Invalid Linear state type '{}' in status_map for {}. Valid types: {}",
+                    state_type,
+                    context,
+                    LINEAR_STATE_TYPES.join(", ")
+                )));
+            }
+        }
+
+        for policy in &rules.default_deadline {
+            if let DefaultDeadlinePolicy::PerPriority(working_days) = policy {
+                if let Some(level) = working_days.keys().find(|level| **level > 4) {
+                    return Err(Error::Validation(format!(
+                        "Invalid Linear priority {} in default_deadline for {}: expected 0-4",
+                        level, context
+                    )));
+                }
+            }
+        }
+
+        if let Some(tshirt) = &rules.time_estimate_strategy.tshirt {
+            for size in tshirt.keys() {
+                let is_size = TSHIRT_SIZES.iter().any(|(_, s)| s == size);
+                if !is_size && size.parse::<f64>().is_err() {
+                    return Err(Error::Validation(format!(
+                        "Invalid t-shirt size '{}' in time_estimate_strategy for {}. Valid sizes: {}",
+                        size,
+                        context,
+                        TSHIRT_SIZES.map(|(_, s)| s).join(", ")
+                    )));
+                }
+            }
+        }
+
+        for label_override in &rules.priority_map.label_overrides {
+            if !MOTION_PRIORITIES.contains(&label_override.priority.as_str()) {
+                return Err(Error::Validation(format!(
+                    "Invalid Motion priority '{}' for label '{}' in priority_map for {}. Valid priorities: {}",
+                    label_override.priority,
+                    label_override.label,
+                    context,
+                    MOTION_PRIORITIES.join(", ")
+                )));
+            }
+        }
+
+        let action = &rules.completion_action;
+        for (field, value) in [
+            ("move_to_state", &action.move_to_state),
+            ("comment", &action.comment),
+        ] {
+            if value.as_deref().is_some_and(|v| v.trim().is_empty()) {
+                return Err(Error::Validation(format!(
+                    "Empty completion_action.{} for {}",
+                    field, context
+                )));
+            }
+        }
+
+        if rules.deleted_task_policy == DeletedTaskPolicy::Label
+            && rules.deleted_task_label.trim().is_empty()
+        {
+            return Err(Error::Validation(format!(
+                "Empty deleted_task_label for {} with the label deleted_task_policy",
+                context
+            )));
+        }
+
+        if let DeletionGuard::Percent(percent) = rules.deletion_guard {
+            if !(percent > 0.0 && percent <= 100.0) {
+                return Err(Error::Validation(format!(
+                    "Invalid deletion_guard percent {} for {}: expected more than 0 and at most 100",
+                    percent, context
+                )));
+            }
+        }
+
+        if rules.schedule_feedback.mode == ScheduleFeedbackMode::Attachment
+            && !rules.schedule_feedback.attachment_url.contains("{task_id}")
+        {
+            return Err(Error::Validation(format!(
+                "schedule_feedback.attachment_url for {} must contain {{task_id}}",
+                context
+            )));
+        }
+
+        Ok(())
+    }
+
     pub fn database_path(&self) -> PathBuf {
-        return self
-            .database_path
+        self.database_path
             .as_deref()
             .unwrap_or(
                 ConfigLoader::get_default_database_path()
@@ -211,7 +1034,7 @@ impl AppConfig {
                     .to_str()
                     .unwrap(),
             )
-            .into();
+            .into()
     }
 
     fn is_valid_time_format(&self, time_str: &str) -> bool {
@@ -227,3 +1050,301 @@ impl AppConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_priority_map_resolution() {
+        let map = PriorityMap {
+            label_overrides: vec![LabelPriorityOverride {
+                label: "incident".to_string(),
+                priority: "ASAP".to_string(),
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(map.resolve(Some(1), []), "ASAP");
+        assert_eq!(map.resolve(Some(4), []), "LOW");
+        assert_eq!(map.resolve(Some(0), []), "MEDIUM");
+        assert_eq!(map.resolve(None, []), "MEDIUM");
+        assert_eq!(map.resolve(Some(42), []), "MEDIUM");
+        assert_eq!(map.resolve(Some(4), ["bug", "incident"]), "ASAP");
+    }
+
+    #[test]
+    fn test_estimate_conversion_uses_team_scale() {
+        let strategy = TimeEstimateStrategy {
+            fibonacci: Some(HashMap::from([
+                ("2".to_string(), 60),
+                ("13".to_string(), 600),
+            ])),
+            exponential: Some(HashMap::from([("2".to_string(), 90)])),
+            tshirt: Some(HashMap::from([
+                ("S".to_string(), 45),
+                ("XXL".to_string(), 900),
+            ])),
+            linear: None,
+            points: None,
+            default_duration_mins: Some(30),
+        };
+
+        let scale = |kind: &str, extended: bool, allow_zero: bool| EstimationScale {
+            kind: kind.to_string(),
+            extended,
+            allow_zero,
+        };
+
+        assert_eq!(
+            strategy.convert_estimate_for_scale(2.0, &scale("fibonacci", false, false)),
+            Some(60)
+        );
+        assert_eq!(
+            strategy.convert_estimate_for_scale(2.0, &scale("exponential", false, false)),
+            Some(90)
+        );
+        assert_eq!(
+            strategy.convert_estimate_for_scale(2.0, &scale("tShirt", false, false)),
+            Some(45)
+        );
+        // No linear mapping configured, so another scale's duration must not be used
+        assert_eq!(
+            strategy.convert_estimate_for_scale(2.0, &scale("linear", false, false)),
+            Some(30)
+        );
+
+        // Extended values are only on the scale when the team enables them
+        assert_eq!(
+            strategy.convert_estimate_for_scale(13.0, &scale("fibonacci", false, false)),
+            Some(30)
+        );
+        assert_eq!(
+            strategy.convert_estimate_for_scale(13.0, &scale("fibonacci", true, false)),
+            Some(600)
+        );
+        assert_eq!(
+            strategy.convert_estimate_for_scale(13.0, &scale("tShirt", true, false)),
+            Some(900)
+        );
+
+        assert!(!scale("linear", false, false).contains(0.0));
+        assert!(scale("linear", false, true).contains(0.0));
+    }
+
+    #[test]
+    fn test_reverse_conversions() {
+        let strategy = TimeEstimateStrategy {
+            fibonacci: Some(HashMap::from([
+                ("1".to_string(), 30),
+                ("2".to_string(), 60),
+                ("3".to_string(), 120),
+            ])),
+            exponential: None,
+            tshirt: None,
+            linear: None,
+            points: None,
+            default_duration_mins: Some(30),
+        };
+        let scale = EstimationScale {
+            kind: "fibonacci".to_string(),
+            extended: false,
+            allow_zero: false,
+        };
+
+        assert_eq!(strategy.estimate_for_duration(60, &scale), Some(2.0));
+        assert_eq!(strategy.estimate_for_duration(100, &scale), Some(3.0));
+        // Ties go to the smaller estimate
+        assert_eq!(strategy.estimate_for_duration(45, &scale), Some(1.0));
+
+        let map = PriorityMap::default();
+        assert_eq!(map.linear_priority("ASAP"), Some(1));
+        assert_eq!(map.linear_priority("MEDIUM"), Some(3));
+        assert_eq!(map.linear_priority("LOW"), Some(4));
+        assert_eq!(map.linear_priority("NONE"), None);
+    }
+
+    #[test]
+    fn test_status_map_resolution() {
+        let map = StatusMap {
+            by_type: HashMap::from([("started".to_string(), "In Progress".to_string())]),
+            by_name: HashMap::from([("In Review".to_string(), "Blocked".to_string())]),
+        };
+
+        assert_eq!(map.resolve("In Progress", "started"), Some("In Progress"));
+        assert_eq!(map.resolve("In Review", "started"), Some("Blocked"));
+        assert_eq!(map.resolve("Todo", "unstarted"), None);
+    }
+
+    #[test]
+    fn test_due_date_uses_timezone() {
+        let rules: SyncRules = serde_json::from_value(serde_json::json!({
+            "default_task_duration_mins": 60,
+            "completed_linear_tag": "motioned",
+            "time_estimate_strategy": { "fibonacci": null, "tshirt": null, "linear": null, "points": null, "default_duration_mins": 60 }
+        }))
+        .unwrap();
+
+        let deadline = rules
+            .deadline_for_due_date("2025-03-14", chrono_tz::America::Los_Angeles)
+            .unwrap();
+        assert_eq!(deadline.to_rfc3339(), "2025-03-15T06:59:00+00:00");
+
+        let deadline = rules
+            .deadline_for_due_date("2025-03-14", chrono_tz::Europe::Berlin)
+            .unwrap();
+        assert_eq!(deadline.to_rfc3339(), "2025-03-14T22:59:00+00:00");
+
+        let rules = SyncRules {
+            due_time: "17:00".to_string(),
+            ..rules
+        };
+        let deadline = rules.deadline_for_due_date("2025-03-14", Tz::UTC).unwrap();
+        assert_eq!(deadline.to_rfc3339(), "2025-03-14T17:00:00+00:00");
+    }
+
+    #[test]
+    fn test_default_deadline_policies() {
+        let rules: SyncRules = serde_json::from_value(serde_json::json!({
+            "default_task_duration_mins": 60,
+            "completed_linear_tag": "motioned",
+            "time_estimate_strategy": { "fibonacci": null, "tshirt": null, "linear": null, "points": null, "default_duration_mins": 60 },
+            "due_time": "17:00",
+            "default_deadline": [
+                "cycle_end",
+                "project_target_date",
+                { "per_priority": { "1": 1, "0": 10 } }
+            ]
+        }))
+        .unwrap();
+
+        // Friday, so working days skip the weekend
+        let anchor = Utc.with_ymd_and_hms(2025, 3, 14, 12, 0, 0).unwrap();
+        let cycle_end = Utc.with_ymd_and_hms(2025, 3, 20, 0, 0, 0).unwrap();
+        let cycle_deadline = Utc.with_ymd_and_hms(2025, 3, 19, 17, 0, 0).unwrap();
+
+        let inputs = DeadlineInputs {
+            anchor,
+            cycle_ends_at: Some(cycle_end),
+            project_target_date: Some("2025-04-01"),
+            priority: Some(1),
+        };
+        assert_eq!(
+            rules.default_deadline(&inputs, Tz::UTC),
+            Some(cycle_deadline)
+        );
+
+        let inputs = DeadlineInputs {
+            cycle_ends_at: None,
+            ..inputs
+        };
+        assert_eq!(
+            rules
+                .default_deadline(&inputs, Tz::UTC)
+                .unwrap()
+                .to_rfc3339(),
+            "2025-04-01T17:00:00+00:00"
+        );
+
+        let inputs = DeadlineInputs {
+            project_target_date: None,
+            ..inputs
+        };
+        assert_eq!(
+            rules
+                .default_deadline(&inputs, Tz::UTC)
+                .unwrap()
+                .to_rfc3339(),
+            "2025-03-17T17:00:00+00:00"
+        );
+
+        let inputs = DeadlineInputs {
+            priority: None,
+            ..inputs
+        };
+        assert_eq!(
+            rules
+                .default_deadline(&inputs, Tz::UTC)
+                .unwrap()
+                .to_rfc3339(),
+            "2025-03-28T17:00:00+00:00"
+        );
+
+        let rules = SyncRules {
+            default_deadline: vec![DefaultDeadlinePolicy::None],
+            ..rules
+        };
+        assert_eq!(rules.default_deadline(&inputs, Tz::UTC), None);
+
+        // `none` wins over any policy listed after it
+        let rules = SyncRules {
+            default_deadline: vec![
+                DefaultDeadlinePolicy::None,
+                DefaultDeadlinePolicy::WorkingDays(5),
+            ],
+            ..rules
+        };
+        assert_eq!(rules.default_deadline(&inputs, Tz::UTC), None);
+    }
+
+    #[test]
+    fn test_schedule_override_uses_timezone() {
+        let work_hours = ScheduleOverride {
+            name: "work_hours".to_string(),
+            interval_seconds: 60,
+            start_time: "09:00".to_string(),
+            end_time: "17:00".to_string(),
+            days: vec!["fri".to_string()],
+        };
+
+        // Friday 16:30 UTC is 09:30 in Los Angeles but 17:30 in Berlin
+        let now = Utc.with_ymd_and_hms(2025, 3, 14, 16, 30, 0).unwrap();
+        assert!(work_hours.is_active_at(now, chrono_tz::America::Los_Angeles));
+        assert!(!work_hours.is_active_at(now, chrono_tz::Europe::Berlin));
+    }
+
+    #[test]
+    fn test_priority_map_validation() {
+        let config_json = r#"{
+            "motion_api_key": "key",
+            "sync_sources": [{ "name": "work", "linear_api_key": "key", "projects": null, "webhook_base_url": null, "sync_rules": null }],
+            "global_sync_rules": {
+                "default_task_duration_mins": 60,
+                "completed_linear_tag": "motioned",
+                "time_estimate_strategy": { "fibonacci": null, "tshirt": null, "linear": null, "points": null, "default_duration_mins": 60 },
+                "priority_map": { "levels": { "1": "URGENT" } }
+            },
+            "database_path": null,
+            "polling_interval_seconds": 300,
+            "schedule_overrides": null
+        }"#;
+
+        let mut config: AppConfig = serde_json::from_str(config_json).unwrap();
+        assert!(config.validate().is_err());
+
+        config
+            .global_sync_rules
+            .priority_map
+            .levels
+            .insert(1, "ASAP".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_deletion_guard() {
+        let guard: DeletionGuard = serde_json::from_str(r#"{ "count": 3 }"#).unwrap();
+        assert!(guard.allows(3, 4));
+        assert!(!guard.allows(4, 100));
+
+        let guard: DeletionGuard = serde_json::from_str(r#"{ "percent": 25.0 }"#).unwrap();
+        assert!(guard.allows(4, 16));
+        assert!(!guard.allows(5, 16));
+        assert!(guard.allows(0, 0));
+        // Up to the minimum, deletions go through whatever the share
+        assert!(guard.allows(3, 3));
+        assert!(!guard.allows(4, 4));
+
+        let guard: DeletionGuard = serde_json::from_str(r#""off""#).unwrap();
+        assert!(guard.allows(100, 100));
+    }
+}
@@ -0,0 +1,289 @@
+use crate::db::schema::{self, SCHEMA_VERSION};
+use crate::{Error, Result};
+use fjall::{TxKeyspace, TxPartitionHandle};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+const EXPORT_FORMAT: &str = "linear-motion-export";
+
+/// First line of an export, describing the records that follow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportHeader {
+    pub format: String,
+    pub schema_version: u32,
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// One stored record, one per line after the header
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportRecord {
+    pub partition: String,
+    pub key: String,
+    pub value: Value,
+}
+
+/// What to do with an imported record whose key already exists
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportConflict {
+    /// Keep the existing record
+    Skip,
+    /// Replace the existing record
+    Overwrite,
+    /// Replace the existing record only if the imported one was updated later
+    Newer,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportSummary {
+    pub imported: usize,
+    /// Records left alone because the key already existed
+    pub skipped: usize,
+    /// Lines that couldn't be read or upgraded, with their line number
+    pub rejected: Vec<(usize, String)>,
+}
+
+/// Write every record from one snapshot of the keyspace as JSON Lines
+pub fn export(keyspace: &TxKeyspace, mut writer: impl Write) -> Result<usize> {
+    let header = ExportHeader {
+        format: EXPORT_FORMAT.to_string(),
+        schema_version: SCHEMA_VERSION,
+        exported_at: chrono::Utc::now(),
+    };
+    serde_json::to_writer(&mut writer, &header)?;
+    writeln!(writer)?;
+
+    // A single read snapshot, so concurrent syncs can't produce a half-written export
+    let read = keyspace.read_tx();
+    let mut exported = 0;
+    for name in schema::record_partitions() {
+        let partition = keyspace.open_partition(name, fjall::PartitionCreateOptions::default())?;
+
+        for item in read.iter(&partition) {
+            let (key, value) = item?;
+            let value = match serde_json::from_slice(&value) {
+                Ok(value) => value,
+                Err(e) => {
+                    warn!(
+                        "Skipping unreadable record {}/{} in export: {}",
+                        name,
+                        String::from_utf8_lossy(&key),
+                        e
+                    );
+                    continue;
+                }
+            };
+            let record = ExportRecord {
+                partition: name.to_string(),
+                key: String::from_utf8_lossy(&key).to_string(),
+                value,
+            };
+            serde_json::to_writer(&mut writer, &record)?;
+            writeln!(writer)?;
+            exported += 1;
+        }
+    }
+    writer.flush()?;
+
+    debug!("Exported {} records", exported);
+    Ok(exported)
+}
+
+/// Read an export and write its records in one commit, upgrading them from the export's schema
+pub fn import(
+    keyspace: &TxKeyspace,
+    reader: impl BufRead,
+    conflict: ImportConflict,
+) -> Result<ImportSummary> {
+    let mut lines = reader.lines();
+    let header: ExportHeader = match lines.next() {
+        Some(line) => serde_json::from_str(&line?)
+            .map_err(|e| Error::Schema(format!("not a linear-motion export: {}", e)))?,
+        None => return Err(Error::Schema("the export is empty".to_string())),
+    };
+    if header.format != EXPORT_FORMAT {
+        return Err(Error::Schema(format!(
+            "unknown export format {}",
+            header.format
+        )));
+    }
+    if header.schema_version > SCHEMA_VERSION {
+        return Err(Error::Schema(format!(
+            "export schema v{} is newer than the supported v{}, upgrade linear-motion",
+            header.schema_version, SCHEMA_VERSION
+        )));
+    }
+
+    let mut partitions: HashMap<&str, TxPartitionHandle> = HashMap::new();
+    for name in schema::record_partitions() {
+        let partition = keyspace.open_partition(name, fjall::PartitionCreateOptions::default())?;
+        partitions.insert(name, partition);
+    }
+
+    let mut summary = ImportSummary::default();
+    let mut tx = keyspace.write_tx();
+    // Line 1 is the header
+    for (line_number, line) in (2..).zip(lines) {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let parsed = serde_json::from_str::<ExportRecord>(&line)
+            .map_err(Error::from)
+            .and_then(|record| {
+                let partition = partitions.get(record.partition.as_str()).ok_or_else(|| {
+                    Error::Schema(format!("unknown partition {}", record.partition))
+                })?;
+                let upgraded = schema::upgrade_record(
+                    &record.partition,
+                    header.schema_version,
+                    record.value.clone(),
+                )?;
+                Ok((partition, record, upgraded))
+            });
+        let (partition, record, upgraded) = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                warn!("Rejected line {} of the import: {}", line_number, e);
+                summary.rejected.push((line_number, e.to_string()));
+                continue;
+            }
+        };
+
+        if let Some(existing) = tx.get(partition, &record.key)? {
+            let replace = match conflict {
+                ImportConflict::Skip => false,
+                ImportConflict::Overwrite => true,
+                ImportConflict::Newer => {
+                    let existing = serde_json::from_slice::<Value>(&existing).ok();
+                    record_time(&record.value) > existing.as_ref().and_then(record_time)
+                }
+            };
+            if !replace {
+                summary.skipped += 1;
+                continue;
+            }
+        }
+
+        tx.insert(partition, record.key.as_str(), upgraded);
+        summary.imported += 1;
+    }
+    tx.commit()?;
+
+    info!(
+        "Imported {} records from an export of {} ({} skipped, {} rejected)",
+        summary.imported,
+        header.exported_at,
+        summary.skipped,
+        summary.rejected.len()
+    );
+    Ok(summary)
+}
+
+/// Export into a new timestamped file in `dir`, only visible once complete
+pub fn backup(keyspace: &TxKeyspace, dir: &Path) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+
+    let name = format!(
+        "linear-motion-{}.jsonl",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S")
+    );
+    let path = dir.join(name);
+    let partial = path.with_extension("jsonl.partial");
+
+    let file = std::fs::File::create(&partial)?;
+    let mut writer = std::io::BufWriter::new(file);
+    export(keyspace, &mut writer)?;
+    writer
+        .into_inner()
+        .map_err(|e| e.into_error())?
+        .sync_all()?;
+    std::fs::rename(&partial, &path)?;
+
+    info!("Database backed up to {}", path.display());
+    Ok(path)
+}
+
+/// When a record was last changed, for the `Newer` conflict policy
+fn record_time(record: &Value) -> Option<chrono::DateTime<chrono::Utc>> {
+    ["updated_at", "deleted_at", "last_sync"]
+        .iter()
+        .find_map(|field| serde_json::from_value(record.get(field)?.clone()).ok())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::db::mapping::TaskMapping;
+
+    fn open() -> (PathBuf, TxKeyspace, TxPartitionHandle) {
+        let path = std::env::temp_dir().join(format!("linear-motion-{}", uuid::Uuid::new_v4()));
+        let keyspace = fjall::Config::new(&path).open_transactional().unwrap();
+        let mappings = keyspace
+            .open_partition("task_mappings", fjall::PartitionCreateOptions::default())
+            .unwrap();
+        (path, keyspace, mappings)
+    }
+
+    fn insert(partition: &TxPartitionHandle, mapping: &TaskMapping) {
+        let key = format!("{}:{}", mapping.sync_source, mapping.linear_issue_id);
+        partition
+            .insert(key, serde_json::to_vec(mapping).unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let (source_path, source, source_mappings) = open();
+        let mut newer = TaskMapping::new(
+            "issue-1".to_string(),
+            "tk_1".to_string(),
+            "personal".to_string(),
+        );
+        newer.updated_at = chrono::Utc::now();
+        insert(&source_mappings, &newer);
+        insert(
+            &source_mappings,
+            &TaskMapping::new(
+                "issue-2".to_string(),
+                "tk_2".to_string(),
+                "personal".to_string(),
+            ),
+        );
+
+        let mut exported = Vec::new();
+        assert_eq!(export(&source, &mut exported).unwrap(), 2);
+        exported.extend_from_slice(b"{\"partition\": \"nope\", \"key\": \"k\", \"value\": {}}\n");
+
+        let (target_path, target, target_mappings) = open();
+        let mut older = newer.clone();
+        older.motion_task_id = Some("tk_old".to_string());
+        older.updated_at = newer.updated_at - chrono::Duration::days(1);
+        insert(&target_mappings, &older);
+
+        let summary = import(&target, exported.as_slice(), ImportConflict::Skip).unwrap();
+        assert_eq!((summary.imported, summary.skipped), (1, 1));
+        assert_eq!(summary.rejected.len(), 1);
+        assert_eq!(summary.rejected[0].0, 4);
+
+        let summary = import(&target, exported.as_slice(), ImportConflict::Newer).unwrap();
+        assert_eq!((summary.imported, summary.skipped), (1, 1));
+        let stored: TaskMapping =
+            serde_json::from_slice(&target_mappings.get("personal:issue-1").unwrap().unwrap())
+                .unwrap();
+        assert_eq!(stored.motion_task_id.as_deref(), Some("tk_1"));
+
+        assert!(matches!(
+            import(&target, &b"{}\n"[..], ImportConflict::Skip),
+            Err(Error::Schema(_))
+        ));
+
+        drop((source_mappings, source, target_mappings, target));
+        let _ = std::fs::remove_dir_all(&source_path);
+        let _ = std::fs::remove_dir_all(&target_path);
+    }
+}
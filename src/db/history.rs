@@ -0,0 +1,225 @@
+use crate::db::mapping::TaskMapping;
+use crate::db::schema::Quarantine;
+use crate::Result;
+use fjall::{TxKeyspace, TxPartitionHandle, WriteTransaction};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use tracing::debug;
+
+/// What happened to an issue's Motion task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SyncEventKind {
+    Created,
+    /// The Motion fields written from Linear
+    Updated {
+        fields: Vec<String>,
+    },
+    Completed {
+        reason: String,
+    },
+    Deleted {
+        reason: String,
+    },
+    Error {
+        message: String,
+    },
+}
+
+impl fmt::Display for SyncEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncEventKind::Created => write!(f, "created"),
+            SyncEventKind::Updated { fields } => write!(f, "updated {}", fields.join(", ")),
+            SyncEventKind::Completed { reason } => write!(f, "completed: {}", reason),
+            SyncEventKind::Deleted { reason } => write!(f, "deleted: {}", reason),
+            SyncEventKind::Error { message } => write!(f, "error: {}", message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncEvent {
+    pub sync_source: String,
+    pub linear_issue_id: String,
+    /// The Linear identifier at the time, e.g. "ENG-123"
+    pub identifier: Option<String>,
+    pub motion_task_id: Option<String>,
+    pub kind: SyncEventKind,
+    pub at: chrono::DateTime<chrono::Utc>,
+}
+
+impl SyncEvent {
+    pub fn new(mapping: &TaskMapping, kind: SyncEventKind) -> Self {
+        Self {
+            sync_source: mapping.sync_source.clone(),
+            linear_issue_id: mapping.linear_issue_id.clone(),
+            identifier: mapping.identifier(),
+            motion_task_id: mapping.motion_task_id.clone(),
+            kind,
+            at: chrono::Utc::now(),
+        }
+    }
+
+    fn issue_prefix(sync_source: &str, linear_issue_id: &str) -> String {
+        format!("{}:{}:", sync_source, linear_issue_id)
+    }
+
+    /// Keyed by issue, then time, so an issue's events are stored as one ordered run
+    fn key(&self) -> String {
+        format!(
+            "{}{}:{}",
+            Self::issue_prefix(&self.sync_source, &self.linear_issue_id),
+            self.at.format("%Y%m%dT%H%M%S%.6fZ"),
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        )
+    }
+}
+
+/// Append-only log of what the sync did to each issue
+pub struct EventLog {
+    keyspace: TxKeyspace,
+    events: TxPartitionHandle,
+    quarantine: Quarantine,
+}
+
+impl EventLog {
+    pub fn new(keyspace: &TxKeyspace, quarantine: Quarantine) -> Result<Self> {
+        let events =
+            keyspace.open_partition("sync_events", fjall::PartitionCreateOptions::default())?;
+
+        debug!("sync event log initialized");
+
+        Ok(Self {
+            keyspace: keyspace.clone(),
+            events,
+            quarantine,
+        })
+    }
+
+    /// Append an event as part of `tx`
+    pub(crate) fn write_event(&self, tx: &mut WriteTransaction, event: &SyncEvent) -> Result<()> {
+        let value = serde_json::to_vec(event)?;
+        tx.insert(&self.events, event.key(), value);
+        Ok(())
+    }
+
+    pub async fn record(&self, event: SyncEvent) -> Result<()> {
+        let value = serde_json::to_vec(&event)?;
+        self.events.insert(event.key(), value)?;
+        debug!(
+            "Recorded {:?} for Linear issue {}",
+            event.kind, event.linear_issue_id
+        );
+        Ok(())
+    }
+
+    /// An issue's events, oldest first
+    pub async fn events_for_issue(
+        &self,
+        sync_source: &str,
+        linear_issue_id: &str,
+    ) -> Result<Vec<SyncEvent>> {
+        let prefix = SyncEvent::issue_prefix(sync_source, linear_issue_id);
+        let mut events = Vec::new();
+
+        for item in self.keyspace.read_tx().prefix(&self.events, prefix) {
+            let (key, value) = item?;
+            if let Some(event) = self.quarantine.decode(&self.events, &key, &value)? {
+                events.push(event);
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// The events of every issue that has carried `identifier`, oldest first
+    pub async fn events_for_identifier(&self, identifier: &str) -> Result<Vec<SyncEvent>> {
+        let mut issues = BTreeSet::new();
+        for item in self.keyspace.read_tx().iter(&self.events) {
+            let (key, value) = item?;
+            let Some(event) = self
+                .quarantine
+                .decode::<SyncEvent>(&self.events, &key, &value)?
+            else {
+                continue;
+            };
+            if event
+                .identifier
+                .as_deref()
+                .is_some_and(|id| id.eq_ignore_ascii_case(identifier))
+            {
+                issues.insert((event.sync_source, event.linear_issue_id));
+            }
+        }
+
+        let mut events = Vec::new();
+        for (sync_source, linear_issue_id) in issues {
+            events.extend(
+                self.events_for_issue(&sync_source, &linear_issue_id)
+                    .await?,
+            );
+        }
+        events.sort_by_key(|event| event.at);
+
+        debug!("Found {} events for {}", events.len(), identifier);
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_events_by_identifier() {
+        let path = std::env::temp_dir().join(format!("linear-motion-{}", uuid::Uuid::new_v4()));
+        let keyspace = fjall::Config::new(&path).open_transactional().unwrap();
+        let log = EventLog::new(&keyspace, Quarantine::new(&keyspace).unwrap()).unwrap();
+
+        let mut mapping = TaskMapping::new(
+            "issue-1".to_string(),
+            "tk_1".to_string(),
+            "personal".to_string(),
+        );
+        mapping.linear_issue_data = serde_json::json!({ "identifier": "ENG-123" });
+        let other = TaskMapping::new(
+            "issue-10".to_string(),
+            "tk_10".to_string(),
+            "personal".to_string(),
+        );
+
+        log.record(SyncEvent::new(&mapping, SyncEventKind::Created))
+            .await
+            .unwrap();
+        log.record(SyncEvent::new(&other, SyncEventKind::Created))
+            .await
+            .unwrap();
+        log.record(SyncEvent::new(
+            &mapping,
+            SyncEventKind::Updated {
+                fields: vec!["dueDate".to_string(), "priority".to_string()],
+            },
+        ))
+        .await
+        .unwrap();
+
+        let events = log.events_for_identifier("eng-123").await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, SyncEventKind::Created);
+        assert_eq!(events[1].kind.to_string(), "updated dueDate, priority");
+
+        // "issue-1" must not pick up the events of "issue-10"
+        assert_eq!(
+            log.events_for_issue("personal", "issue-1")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        drop((log, keyspace));
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}
@@ -1,15 +1,18 @@
+use crate::clients::motion::{MotionTask, TaskDuration, TaskUpdate};
+use crate::db::schema::Quarantine;
 use crate::Result;
-use fjall::{Keyspace, PartitionHandle, PersistMode};
+use fjall::{PersistMode, TxKeyspace, TxPartitionHandle, WriteTransaction};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
-use tracing::{debug, info};
+use tracing::debug;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MappingStatus {
-    Pending, // Linear issue fetched, Motion task creation pending
-    Synced,  // Motion task created successfully
-    Failed,  // Motion task creation failed
-    Stale,   // Linear issue may have been updated, needs re-sync
+    Pending,   // Linear issue fetched, Motion task creation pending
+    Synced,    // Motion task created successfully
+    Failed,    // Motion task creation failed
+    Stale,     // Linear issue may have been updated, needs re-sync
+    Completed, // Work finished in Motion or Linear, kept to detect reopened issues
+    Ignored,   // Motion task was deleted by the user, don't recreate it until resumed
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,29 +26,336 @@ pub struct TaskMapping {
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub last_sync_attempt: Option<chrono::DateTime<chrono::Utc>>,
     pub sync_error: Option<String>,
+    #[serde(default)]
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub schedule_feedback: Option<ScheduleFeedbackRecord>,
+    #[serde(default)]
+    pub at_risk: Option<AtRisk>,
+    /// The Motion task as it was after our last write, to tell edits made in Motion apart
+    #[serde(default)]
+    pub motion_snapshot: Option<MotionSnapshot>,
+    /// The fields last computed from Linear, the base for three-way merges
+    #[serde(default)]
+    pub last_projection: Option<TaskUpdate>,
+    #[serde(default)]
+    pub conflicts: Vec<FieldConflict>,
+    #[serde(default)]
+    pub downstream_deletion: Option<DownstreamDeletion>,
+    /// Written before a Motion task is created, cleared once the task id is stored
+    #[serde(default)]
+    pub create_intent: Option<CreateIntent>,
+    /// When the deletion guard first held back deleting this mapping's Motion task
+    #[serde(default)]
+    pub deletion_held_since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A Motion task creation that may have happened without its id being recorded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateIntent {
+    /// The Linear identifier the task name is prefixed with, e.g. "CAR-126"
+    pub identifier: String,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl CreateIntent {
+    /// Whether `task` carries our marker label and this intent's identifier
+    pub fn matches(&self, task: &MotionTask) -> bool {
+        let has_marker = task
+            .labels
+            .iter()
+            .flatten()
+            .any(|label| label.name == "linear-sync");
+        has_marker && task.name.starts_with(&format!("[{}] ", self.identifier))
+    }
+}
+
+/// What became of a mapping whose Motion task was deleted outside of the sync
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeletionOutcome {
+    /// A new Motion task is created on the next sync
+    Recreate,
+    /// The issue is no longer synced
+    Ignore,
+    /// The issue is no longer synced and was labelled in Linear
+    Label,
+}
+
+/// The Motion task was deleted outside of the sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownstreamDeletion {
+    pub motion_task_id: String,
+    pub detected_at: chrono::DateTime<chrono::Utc>,
+    pub outcome: DeletionOutcome,
+}
+
+/// A field changed in both Linear and Motion where Motion's edit was kept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldConflict {
+    /// Motion API field name, e.g. "dueDate"
+    pub field: String,
+    /// The value Linear wanted, as JSON
+    pub linear_value: serde_json::Value,
+    pub detected_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MotionSnapshot {
+    pub updated_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub name: String,
+    pub description: Option<String>,
+    pub duration_mins: Option<u32>,
+    pub priority: Option<String>,
+    pub due_date: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+impl MotionSnapshot {
+    /// The Motion API names of the fields that differ in `current`
+    pub fn edited_fields(&self, current: &MotionSnapshot) -> Vec<String> {
+        [
+            ("name", self.name != current.name),
+            ("description", self.description != current.description),
+            ("duration", self.duration_mins != current.duration_mins),
+            ("priority", self.priority != current.priority),
+            ("dueDate", self.due_date != current.due_date),
+            ("status", self.status != current.status),
+        ]
+        .into_iter()
+        .filter(|(_, edited)| *edited)
+        .map(|(field, _)| field.to_string())
+        .collect()
+    }
+
+    /// A copy with the given fields (Motion API names) taken from `current`
+    pub fn with_fields_from(&self, current: &MotionSnapshot, fields: &[String]) -> MotionSnapshot {
+        let mut snapshot = self.clone();
+        for field in fields {
+            match field.as_str() {
+                "name" => snapshot.name = current.name.clone(),
+                "description" => snapshot.description = current.description.clone(),
+                "duration" => snapshot.duration_mins = current.duration_mins,
+                "priority" => snapshot.priority = current.priority.clone(),
+                "dueDate" => snapshot.due_date = current.due_date,
+                "status" => snapshot.status = current.status.clone(),
+                _ => {}
+            }
+        }
+        snapshot
+    }
+
+    /// The Motion API names of the fields set in `projection` that no longer hold its value.
+    /// The description is left out since Motion stores it reformatted.
+    pub fn edited_since(&self, projection: &TaskUpdate) -> Vec<String> {
+        let duration_mins = match projection.duration {
+            Some(TaskDuration::Minutes(minutes)) => Some(minutes),
+            _ => None,
+        };
+        [
+            (
+                "name",
+                projection
+                    .name
+                    .as_ref()
+                    .is_some_and(|name| *name != self.name),
+            ),
+            (
+                "duration",
+                projection.duration.is_some() && duration_mins != self.duration_mins,
+            ),
+            (
+                "priority",
+                projection.priority.is_some() && projection.priority != self.priority,
+            ),
+            (
+                "dueDate",
+                projection.due_date.is_some() && projection.due_date != self.due_date,
+            ),
+            (
+                "status",
+                projection.status.is_some() && projection.status != self.status,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, edited)| *edited)
+        .map(|(field, _)| field.to_string())
+        .collect()
+    }
+}
+
+impl From<&MotionTask> for MotionSnapshot {
+    fn from(task: &MotionTask) -> Self {
+        Self {
+            updated_time: task.updated_time,
+            name: task.name.clone(),
+            description: task.description.clone(),
+            duration_mins: match task.duration {
+                Some(TaskDuration::Minutes(minutes)) => Some(minutes),
+                _ => None,
+            },
+            priority: task.priority.clone(),
+            due_date: task.due_date,
+            status: task.status.as_ref().map(|status| status.name.clone()),
+        }
+    }
+}
+
+/// Motion can't get the task done by the Linear deadline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtRisk {
+    pub reason: String,
+    pub since: chrono::DateTime<chrono::Utc>,
+}
+
+/// Where a schedule summary lives on the Linear issue
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackTarget {
+    Comment,
+    Attachment,
+}
+
+/// The schedule summary last posted to the Linear issue
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScheduleFeedbackRecord {
+    pub mode: FeedbackTarget,
+    /// The Linear comment or attachment holding the summary
+    pub linear_id: String,
+    pub text: String,
+}
+
+/// A Motion task deleted by the sync, kept until the retention period ends so it can be restored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tombstone {
+    pub motion_task: MotionTask,
+    /// The mapping as it was before the deletion, if the task had one
+    pub mapping: Option<TaskMapping>,
+    /// Why the sync deleted the task
+    pub reason: String,
+    pub deleted_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Tombstone {
+    pub fn motion_task_id(&self) -> &str {
+        self.motion_task.id.as_deref().unwrap_or_default()
+    }
+
+    /// The Linear identifier of the deleted task's issue
+    pub fn identifier(&self) -> Option<String> {
+        self.mapping
+            .as_ref()
+            .and_then(TaskMapping::identifier)
+            .or_else(|| self.motion_task.linear_identifier())
+    }
 }
 
 pub struct MappingStore {
-    keyspace: Keyspace,
-    mappings: PartitionHandle,
+    keyspace: TxKeyspace,
+    mappings: TxPartitionHandle,
+    /// Motion task id -> mapping key
+    motion_index: TxPartitionHandle,
+    tombstones: TxPartitionHandle,
+    quarantine: Quarantine,
 }
 
 impl MappingStore {
-    pub async fn new<P: AsRef<Path>>(db_path: P) -> Result<Self> {
-        let keyspace = fjall::Config::new(db_path).open()?;
+    pub fn new(keyspace: &TxKeyspace, quarantine: Quarantine) -> Result<Self> {
         let mappings =
             keyspace.open_partition("task_mappings", fjall::PartitionCreateOptions::default())?;
+        let index_exists = keyspace.partition_exists("motion_index");
+        let motion_index =
+            keyspace.open_partition("motion_index", fjall::PartitionCreateOptions::default())?;
+        let tombstones =
+            keyspace.open_partition("tombstones", fjall::PartitionCreateOptions::default())?;
+
+        let store = Self {
+            keyspace: keyspace.clone(),
+            mappings,
+            motion_index,
+            tombstones,
+            quarantine,
+        };
+
+        // Databases from before the index existed need it built once
+        if !index_exists {
+            store.rebuild_motion_index()?;
+        }
 
         debug!("task mapping store initialized");
 
-        Ok(Self { keyspace, mappings })
+        Ok(store)
     }
 
-    pub async fn store_mapping(&self, mapping: TaskMapping) -> Result<()> {
+    /// Recreate the Motion task id index from the stored mappings
+    pub fn rebuild_motion_index(&self) -> Result<()> {
+        let read = self.keyspace.read_tx();
+        let mut tx = self.keyspace.write_tx();
+        for item in read.iter(&self.motion_index) {
+            let (key, _) = item?;
+            tx.remove(&self.motion_index, key);
+        }
+
+        let mut indexed = 0;
+        for item in read.iter(&self.mappings) {
+            let (key, value) = item?;
+            let mapping: TaskMapping = serde_json::from_slice(&value)?;
+            if let Some(motion_task_id) = &mapping.motion_task_id {
+                tx.insert(&self.motion_index, motion_task_id.as_str(), key);
+                indexed += 1;
+            }
+        }
+        tx.commit()?;
+
+        debug!("Rebuilt Motion task index with {} entries", indexed);
+        Ok(())
+    }
+
+    /// Write a mapping and its index entry as part of `tx`
+    pub(crate) fn write_mapping(
+        &self,
+        tx: &mut WriteTransaction,
+        mapping: &TaskMapping,
+    ) -> Result<()> {
         let key = format!("{}:{}", mapping.sync_source, mapping.linear_issue_id);
-        let value = serde_json::to_vec(&mapping)?;
+        let value = serde_json::to_vec(mapping)?;
+
+        if let Some(previous) = tx.get(&self.mappings, &key)? {
+            let previous: TaskMapping = serde_json::from_slice(&previous)?;
+            if let Some(previous_id) = previous.motion_task_id {
+                if mapping.motion_task_id.as_ref() != Some(&previous_id) {
+                    tx.remove(&self.motion_index, previous_id);
+                }
+            }
+        }
+        if let Some(motion_task_id) = &mapping.motion_task_id {
+            tx.insert(&self.motion_index, motion_task_id.as_str(), key.as_str());
+        }
+        tx.insert(&self.mappings, key.as_str(), value);
+
+        Ok(())
+    }
+
+    /// Read a mapping as part of `tx`, so it can be changed and written back in the same commit
+    pub(crate) fn read_mapping(
+        &self,
+        tx: &WriteTransaction,
+        sync_source: &str,
+        linear_issue_id: &str,
+    ) -> Result<Option<TaskMapping>> {
+        let key = format!("{}:{}", sync_source, linear_issue_id);
+        match tx.get(&self.mappings, &key)? {
+            Some(value) => Ok(Some(serde_json::from_slice(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn store_mapping(&self, mapping: TaskMapping) -> Result<()> {
+        let mut tx = self.keyspace.write_tx();
+        self.write_mapping(&mut tx, &mapping)?;
+        tx.commit()?;
 
-        self.mappings.insert(&key, &value)?;
         debug!(
             "Stored mapping: {} -> {:?} (status: {:?})",
             mapping.linear_issue_id, mapping.motion_task_id, mapping.status
@@ -61,9 +371,15 @@ impl MappingStore {
     ) -> Result<Option<TaskMapping>> {
         let key = format!("{}:{}", sync_source, linear_issue_id);
 
-        match self.mappings.get(&key)? {
-            Some(value) => {
-                let mapping: TaskMapping = serde_json::from_slice(&value)?;
+        let mapping: Option<TaskMapping> = match self.mappings.get(&key)? {
+            Some(value) => self
+                .quarantine
+                .decode(&self.mappings, key.as_bytes(), &value)?,
+            None => None,
+        };
+
+        match mapping {
+            Some(mapping) => {
                 debug!(
                     "Found mapping: {} -> {:?}",
                     linear_issue_id, mapping.motion_task_id
@@ -81,18 +397,22 @@ impl MappingStore {
         &self,
         motion_task_id: &str,
     ) -> Result<Option<TaskMapping>> {
-        // Since we need to search by Motion task ID, we'll iterate through all mappings
-        // For better performance in a production system, we might want to maintain a reverse index
-        for item in self.mappings.iter() {
-            let (_, value) = item?;
-            let mapping: TaskMapping = serde_json::from_slice(&value)?;
+        if let Some(key) = self.motion_index.get(motion_task_id)? {
+            if let Some(value) = self.mappings.get(&key)? {
+                let Some(mapping) =
+                    self.quarantine
+                        .decode::<TaskMapping>(&self.mappings, &key, &value)?
+                else {
+                    return Ok(None);
+                };
 
-            if mapping.motion_task_id.as_deref() == Some(motion_task_id) {
-                debug!(
-                    "Found mapping by Motion ID: {} -> {}",
-                    motion_task_id, mapping.linear_issue_id
-                );
-                return Ok(Some(mapping));
+                if mapping.motion_task_id.as_deref() == Some(motion_task_id) {
+                    debug!(
+                        "Found mapping by Motion ID: {} -> {}",
+                        motion_task_id, mapping.linear_issue_id
+                    );
+                    return Ok(Some(mapping));
+                }
             }
         }
 
@@ -107,13 +427,18 @@ impl MappingStore {
     ) -> Result<Option<TaskMapping>> {
         let key = format!("{}:{}", sync_source, linear_issue_id);
 
-        let existing = match self.mappings.get(&key)? {
+        let existing: Option<TaskMapping> = match self.mappings.get(&key)? {
             Some(value) => Some(serde_json::from_slice(&value)?),
             None => None,
         };
 
-        if existing.is_some() {
-            self.mappings.remove(&key)?;
+        if let Some(mapping) = &existing {
+            let mut tx = self.keyspace.write_tx();
+            if let Some(motion_task_id) = &mapping.motion_task_id {
+                tx.remove(&self.motion_index, motion_task_id.as_str());
+            }
+            tx.remove(&self.mappings, key.as_str());
+            tx.commit()?;
             debug!("Removed mapping for Linear issue: {}", linear_issue_id);
         }
 
@@ -124,13 +449,14 @@ impl MappingStore {
         let prefix = format!("{}:", sync_source);
         let mut mappings = Vec::new();
 
-        for item in self.mappings.iter() {
+        for item in self.keyspace.read_tx().iter(&self.mappings) {
             let (key, value) = item?;
             let key_str = String::from_utf8_lossy(&key);
 
             if key_str.starts_with(&prefix) {
-                let mapping: TaskMapping = serde_json::from_slice(&value)?;
-                mappings.push(mapping);
+                if let Some(mapping) = self.quarantine.decode(&self.mappings, &key, &value)? {
+                    mappings.push(mapping);
+                }
             }
         }
 
@@ -145,10 +471,11 @@ impl MappingStore {
     pub async fn list_all_mappings(&self) -> Result<Vec<TaskMapping>> {
         let mut mappings = Vec::new();
 
-        for item in self.mappings.iter() {
-            let (_, value) = item?;
-            let mapping: TaskMapping = serde_json::from_slice(&value)?;
-            mappings.push(mapping);
+        for item in self.keyspace.read_tx().iter(&self.mappings) {
+            let (key, value) = item?;
+            if let Some(mapping) = self.quarantine.decode(&self.mappings, &key, &value)? {
+                mappings.push(mapping);
+            }
         }
 
         debug!("Found {} total mappings", mappings.len());
@@ -180,6 +507,15 @@ impl MappingStore {
             updated_at: chrono::Utc::now(),
             last_sync_attempt: None,
             sync_error: None,
+            completed_at: None,
+            schedule_feedback: None,
+            at_risk: None,
+            motion_snapshot: None,
+            last_projection: None,
+            conflicts: Vec::new(),
+            downstream_deletion: None,
+            create_intent: None,
+            deletion_held_since: None,
         };
 
         self.store_mapping(mapping.clone()).await?;
@@ -198,10 +534,7 @@ impl MappingStore {
             .get_mapping_by_linear_id(sync_source, linear_issue_id)
             .await?
         {
-            mapping.motion_task_id = Some(motion_task_id);
-            mapping.status = MappingStatus::Synced;
-            mapping.updated_at = chrono::Utc::now();
-            mapping.sync_error = None;
+            mapping.set_synced(motion_task_id);
 
             self.store_mapping(mapping).await?;
             debug!("Marked mapping as synced: {}", linear_issue_id);
@@ -209,6 +542,31 @@ impl MappingStore {
         Ok(())
     }
 
+    /// Durably record that a Motion task is about to be created for this mapping
+    pub async fn record_create_intent(
+        &self,
+        sync_source: &str,
+        linear_issue_id: &str,
+        identifier: &str,
+    ) -> Result<()> {
+        if let Some(mut mapping) = self
+            .get_mapping_by_linear_id(sync_source, linear_issue_id)
+            .await?
+        {
+            mapping.create_intent = Some(CreateIntent {
+                identifier: identifier.to_string(),
+                started_at: chrono::Utc::now(),
+            });
+            mapping.updated_at = chrono::Utc::now();
+
+            self.store_mapping(mapping).await?;
+            // The intent must survive a crash during the create request
+            self.keyspace.persist(PersistMode::SyncAll)?;
+            debug!("Recorded create intent for: {}", linear_issue_id);
+        }
+        Ok(())
+    }
+
     /// Update mapping when sync fails
     pub async fn mark_failed(
         &self,
@@ -220,10 +578,7 @@ impl MappingStore {
             .get_mapping_by_linear_id(sync_source, linear_issue_id)
             .await?
         {
-            mapping.status = MappingStatus::Failed;
-            mapping.updated_at = chrono::Utc::now();
-            mapping.last_sync_attempt = Some(chrono::Utc::now());
-            mapping.sync_error = Some(error);
+            mapping.set_failed(error);
 
             self.store_mapping(mapping).await?;
             debug!("Marked mapping as failed: {}", linear_issue_id);
@@ -231,6 +586,216 @@ impl MappingStore {
         Ok(())
     }
 
+    /// Mark a mapping as completed, keeping it around so a reopened issue can be detected
+    pub async fn mark_completed(
+        &self,
+        sync_source: &str,
+        linear_issue_id: &str,
+        issue: Option<&crate::clients::linear::LinearIssue>,
+    ) -> Result<()> {
+        if let Some(mut mapping) = self
+            .get_mapping_by_linear_id(sync_source, linear_issue_id)
+            .await?
+        {
+            if let Some(issue) = issue {
+                mapping.linear_issue_data = serde_json::to_value(issue)?;
+            }
+            let now = chrono::Utc::now();
+            mapping.status = MappingStatus::Completed;
+            mapping.updated_at = now;
+            mapping.completed_at.get_or_insert(now);
+
+            self.store_mapping(mapping).await?;
+            debug!("Marked mapping as completed: {}", linear_issue_id);
+        }
+        Ok(())
+    }
+
+    /// Record the schedule summary posted to the Linear issue
+    pub async fn set_schedule_feedback(
+        &self,
+        sync_source: &str,
+        linear_issue_id: &str,
+        record: ScheduleFeedbackRecord,
+    ) -> Result<()> {
+        if let Some(mut mapping) = self
+            .get_mapping_by_linear_id(sync_source, linear_issue_id)
+            .await?
+        {
+            mapping.schedule_feedback = Some(record);
+            mapping.updated_at = chrono::Utc::now();
+
+            self.store_mapping(mapping).await?;
+            debug!("Updated schedule feedback for mapping: {}", linear_issue_id);
+        }
+        Ok(())
+    }
+
+    /// Record whether the task is at risk, keeping the original detection time while it stays so
+    pub async fn set_at_risk(
+        &self,
+        sync_source: &str,
+        linear_issue_id: &str,
+        reason: Option<String>,
+    ) -> Result<()> {
+        if let Some(mut mapping) = self
+            .get_mapping_by_linear_id(sync_source, linear_issue_id)
+            .await?
+        {
+            mapping.at_risk = reason.map(|reason| AtRisk {
+                reason,
+                since: mapping
+                    .at_risk
+                    .as_ref()
+                    .map_or_else(chrono::Utc::now, |at_risk| at_risk.since),
+            });
+            mapping.updated_at = chrono::Utc::now();
+
+            self.store_mapping(mapping).await?;
+            debug!("Updated at-risk state for mapping: {}", linear_issue_id);
+        }
+        Ok(())
+    }
+
+    /// Attach an existing Motion task to the issue's mapping, creating the mapping if needed.
+    /// The mapping is left stale so the next sync writes Linear's values to the task.
+    pub async fn adopt_task(
+        &self,
+        sync_source: &str,
+        issue: &crate::clients::linear::LinearIssue,
+        task: &MotionTask,
+    ) -> Result<()> {
+        let mut mapping = match self
+            .get_mapping_by_linear_id(sync_source, &issue.id)
+            .await?
+        {
+            Some(mapping) => mapping,
+            None => self.create_pending_mapping(sync_source, issue).await?,
+        };
+
+        mapping.motion_task_id = task.id.clone();
+        mapping.status = MappingStatus::Stale;
+        mapping.linear_issue_data = serde_json::to_value(issue)?;
+        mapping.motion_snapshot = Some(MotionSnapshot::from(task));
+        mapping.last_projection = None;
+        mapping.conflicts.clear();
+        mapping.downstream_deletion = None;
+        mapping.create_intent = None;
+        mapping.sync_error = None;
+        mapping.updated_at = chrono::Utc::now();
+
+        self.store_mapping(mapping).await?;
+        debug!(
+            "Adopted Motion task {:?} for Linear issue: {}",
+            task.id, issue.id
+        );
+        Ok(())
+    }
+
+    /// Mark the mapping's Motion task deletion as held by the deletion guard, or release it
+    pub async fn set_deletion_held(
+        &self,
+        sync_source: &str,
+        linear_issue_id: &str,
+        held: bool,
+    ) -> Result<()> {
+        if let Some(mut mapping) = self
+            .get_mapping_by_linear_id(sync_source, linear_issue_id)
+            .await?
+        {
+            mapping.deletion_held_since = match (held, mapping.deletion_held_since) {
+                (true, since) => Some(since.unwrap_or_else(chrono::Utc::now)),
+                (false, _) => None,
+            };
+            mapping.updated_at = chrono::Utc::now();
+
+            self.store_mapping(mapping).await?;
+            debug!("Updated held deletion for mapping: {}", linear_issue_id);
+        }
+        Ok(())
+    }
+
+    /// Record the Motion task as it stands after a sync
+    pub async fn set_motion_snapshot(
+        &self,
+        sync_source: &str,
+        linear_issue_id: &str,
+        task: &MotionTask,
+    ) -> Result<()> {
+        if let Some(mut mapping) = self
+            .get_mapping_by_linear_id(sync_source, linear_issue_id)
+            .await?
+        {
+            mapping.set_motion_snapshot(task);
+
+            self.store_mapping(mapping).await?;
+            debug!("Updated Motion snapshot for mapping: {}", linear_issue_id);
+        }
+        Ok(())
+    }
+
+    /// Record the projection a merge was based on
+    pub async fn record_projection(
+        &self,
+        sync_source: &str,
+        linear_issue_id: &str,
+        projection: TaskUpdate,
+        written: &TaskUpdate,
+        conflicts: Vec<FieldConflict>,
+    ) -> Result<()> {
+        if let Some(mut mapping) = self
+            .get_mapping_by_linear_id(sync_source, linear_issue_id)
+            .await?
+        {
+            mapping.record_projection(projection, written, conflicts);
+
+            self.store_mapping(mapping).await?;
+            debug!("Recorded projection for mapping: {}", linear_issue_id);
+        }
+        Ok(())
+    }
+
+    /// Record that the Motion task was deleted outside of the sync
+    pub async fn mark_deleted_downstream(
+        &self,
+        sync_source: &str,
+        linear_issue_id: &str,
+        outcome: DeletionOutcome,
+    ) -> Result<()> {
+        if let Some(mut mapping) = self
+            .get_mapping_by_linear_id(sync_source, linear_issue_id)
+            .await?
+        {
+            mapping.mark_deleted_downstream(outcome);
+
+            self.store_mapping(mapping).await?;
+            debug!(
+                "Marked mapping as deleted downstream ({:?}): {}",
+                outcome, linear_issue_id
+            );
+        }
+        Ok(())
+    }
+
+    /// Put an ignored mapping back to pending so the next sync creates a new Motion task
+    pub async fn resume_ignored(&self, sync_source: &str, linear_issue_id: &str) -> Result<bool> {
+        let Some(mut mapping) = self
+            .get_mapping_by_linear_id(sync_source, linear_issue_id)
+            .await?
+            .filter(|mapping| matches!(mapping.status, MappingStatus::Ignored))
+        else {
+            return Ok(false);
+        };
+
+        mapping.status = MappingStatus::Pending;
+        mapping.downstream_deletion = None;
+        mapping.updated_at = chrono::Utc::now();
+
+        self.store_mapping(mapping).await?;
+        debug!("Resumed ignored mapping: {}", linear_issue_id);
+        Ok(true)
+    }
+
     /// Update the stored Linear issue data in a mapping (used for re-sync)
     pub async fn update_issue_data(
         &self,
@@ -242,8 +807,7 @@ impl MappingStore {
             .get_mapping_by_linear_id(sync_source, linear_issue_id)
             .await?
         {
-            mapping.linear_issue_data = serde_json::to_value(issue)?;
-            mapping.updated_at = chrono::Utc::now();
+            mapping.set_issue_data(issue)?;
 
             self.store_mapping(mapping).await?;
             debug!("Updated issue data for mapping: {}", linear_issue_id);
@@ -255,9 +819,14 @@ impl MappingStore {
     pub async fn list_mappings_by_status(&self, status: MappingStatus) -> Result<Vec<TaskMapping>> {
         let mut mappings = Vec::new();
 
-        for item in self.mappings.iter() {
-            let (_, value) = item?;
-            let mapping: TaskMapping = serde_json::from_slice(&value)?;
+        for item in self.keyspace.read_tx().iter(&self.mappings) {
+            let (key, value) = item?;
+            let Some(mapping) =
+                self.quarantine
+                    .decode::<TaskMapping>(&self.mappings, &key, &value)?
+            else {
+                continue;
+            };
 
             if std::mem::discriminant(&mapping.status) == std::mem::discriminant(&status) {
                 mappings.push(mapping);
@@ -272,13 +841,166 @@ impl MappingStore {
         Ok(mappings)
     }
 
-    pub async fn flush(&self) -> Result<()> {
-        self.keyspace.persist(PersistMode::SyncAll)?;
+    /// Keep a deleted Motion task and its mapping, keyed by the Motion task id
+    pub async fn store_tombstone(&self, tombstone: &Tombstone) -> Result<()> {
+        let value = serde_json::to_vec(tombstone)?;
+
+        self.tombstones.insert(tombstone.motion_task_id(), &value)?;
+        debug!(
+            "Stored tombstone for Motion task: {}",
+            tombstone.motion_task_id()
+        );
+        Ok(())
+    }
+
+    pub async fn get_tombstone(&self, motion_task_id: &str) -> Result<Option<Tombstone>> {
+        match self.tombstones.get(motion_task_id)? {
+            Some(value) => {
+                self.quarantine
+                    .decode(&self.tombstones, motion_task_id.as_bytes(), &value)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// All tombstones, most recently deleted first
+    pub async fn list_tombstones(&self) -> Result<Vec<Tombstone>> {
+        let mut tombstones: Vec<Tombstone> = Vec::new();
+
+        for item in self.keyspace.read_tx().iter(&self.tombstones) {
+            let (key, value) = item?;
+            if let Some(tombstone) = self.quarantine.decode(&self.tombstones, &key, &value)? {
+                tombstones.push(tombstone);
+            }
+        }
+
+        tombstones.sort_by_key(|tombstone| std::cmp::Reverse(tombstone.deleted_at));
+        Ok(tombstones)
+    }
+
+    pub async fn remove_tombstone(&self, motion_task_id: &str) -> Result<()> {
+        self.tombstones.remove(motion_task_id)?;
+        debug!("Removed tombstone for Motion task: {}", motion_task_id);
         Ok(())
     }
+
+    /// Drop tombstones older than the retention period, returning how many were removed
+    pub async fn purge_tombstones(&self, retention_days: u32) -> Result<u64> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(retention_days as i64);
+        let mut removed = 0;
+
+        for tombstone in self.list_tombstones().await? {
+            if tombstone.deleted_at < cutoff {
+                self.remove_tombstone(tombstone.motion_task_id()).await?;
+                removed += 1;
+            }
+        }
+
+        debug!("Purged {} expired tombstones", removed);
+        Ok(removed)
+    }
+
+    /// Point a mapping at a Motion task recreated from a tombstone
+    pub async fn relink_restored(&self, mut mapping: TaskMapping, task: &MotionTask) -> Result<()> {
+        mapping.motion_task_id = task.id.clone();
+        mapping.status = MappingStatus::Synced;
+        mapping.motion_snapshot = Some(MotionSnapshot::from(task));
+        mapping.completed_at = None;
+        mapping.downstream_deletion = None;
+        mapping.deletion_held_since = None;
+        mapping.create_intent = None;
+        mapping.sync_error = None;
+        mapping.updated_at = chrono::Utc::now();
+
+        self.store_mapping(mapping).await
+    }
 }
 
 impl TaskMapping {
+    /// The Linear identifier from the stored issue data, e.g. "ENG-123"
+    pub fn identifier(&self) -> Option<String> {
+        self.linear_issue_data
+            .get("identifier")
+            .and_then(|identifier| identifier.as_str())
+            .map(str::to_string)
+    }
+
+    /// Record the Motion task the issue was synced to
+    pub fn set_synced(&mut self, motion_task_id: String) {
+        self.motion_task_id = Some(motion_task_id);
+        self.status = MappingStatus::Synced;
+        self.updated_at = chrono::Utc::now();
+        self.sync_error = None;
+        self.create_intent = None;
+    }
+
+    pub fn set_failed(&mut self, error: String) {
+        self.status = MappingStatus::Failed;
+        self.updated_at = chrono::Utc::now();
+        self.last_sync_attempt = Some(chrono::Utc::now());
+        self.sync_error = Some(error);
+    }
+
+    /// Record a failed update of an existing task. The mapping stays synced to it.
+    pub fn set_update_failed(&mut self, error: String) {
+        self.updated_at = chrono::Utc::now();
+        self.last_sync_attempt = Some(chrono::Utc::now());
+        self.sync_error = Some(error);
+    }
+
+    pub fn set_issue_data(&mut self, issue: &crate::clients::linear::LinearIssue) -> Result<()> {
+        self.linear_issue_data = serde_json::to_value(issue)?;
+        self.updated_at = chrono::Utc::now();
+        Ok(())
+    }
+
+    /// Snapshot the Motion task as it stands after a sync
+    pub fn set_motion_snapshot(&mut self, task: &MotionTask) {
+        self.motion_snapshot = Some(MotionSnapshot::from(task));
+        self.updated_at = chrono::Utc::now();
+    }
+
+    /// Record the projection a merge was based on. Conflicts on written fields are resolved,
+    /// new ones replace any earlier conflict on the same field.
+    pub fn record_projection(
+        &mut self,
+        projection: TaskUpdate,
+        written: &TaskUpdate,
+        conflicts: Vec<FieldConflict>,
+    ) {
+        let written = written.fields();
+        self.conflicts.retain(|conflict| {
+            !written.contains(&conflict.field)
+                && !conflicts.iter().any(|c| c.field == conflict.field)
+        });
+        self.conflicts.extend(conflicts);
+        self.last_projection = Some(projection);
+        self.updated_at = chrono::Utc::now();
+    }
+
+    /// Forget the deleted Motion task. Under the recreate policy the mapping goes back to
+    /// pending so the next sync creates a fresh task.
+    pub fn mark_deleted_downstream(&mut self, outcome: DeletionOutcome) {
+        let now = chrono::Utc::now();
+        if let Some(motion_task_id) = self.motion_task_id.take() {
+            self.downstream_deletion = Some(DownstreamDeletion {
+                motion_task_id,
+                detected_at: now,
+                outcome,
+            });
+        }
+        self.status = match outcome {
+            DeletionOutcome::Recreate => MappingStatus::Pending,
+            DeletionOutcome::Ignore | DeletionOutcome::Label => MappingStatus::Ignored,
+        };
+        self.motion_snapshot = None;
+        self.last_projection = None;
+        self.conflicts.clear();
+        self.schedule_feedback = None;
+        self.at_risk = None;
+        self.updated_at = now;
+    }
+
     pub fn new(linear_issue_id: String, motion_task_id: String, sync_source: String) -> Self {
         let now = chrono::Utc::now();
 
@@ -292,6 +1014,347 @@ impl TaskMapping {
             updated_at: now,
             last_sync_attempt: Some(now),
             sync_error: None,
+            completed_at: None,
+            schedule_feedback: None,
+            at_risk: None,
+            motion_snapshot: None,
+            last_projection: None,
+            conflicts: Vec::new(),
+            downstream_deletion: None,
+            create_intent: None,
+            deletion_held_since: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_motion_id_index() {
+        let path = std::env::temp_dir().join(format!("linear-motion-{}", uuid::Uuid::new_v4()));
+        let keyspace = fjall::Config::new(&path).open_transactional().unwrap();
+        let store = MappingStore::new(&keyspace, Quarantine::new(&keyspace).unwrap()).unwrap();
+
+        let mapping = TaskMapping::new(
+            "issue-1".to_string(),
+            "tk_1".to_string(),
+            "personal".to_string(),
+        );
+        store.store_mapping(mapping.clone()).await.unwrap();
+        let found = store.get_mapping_by_motion_id("tk_1").await.unwrap();
+        assert_eq!(found.unwrap().linear_issue_id, "issue-1");
+
+        // Relinking drops the old index entry
+        store
+            .mark_synced("personal", "issue-1", "tk_2".to_string())
+            .await
+            .unwrap();
+        assert!(store
+            .get_mapping_by_motion_id("tk_1")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(store
+            .get_mapping_by_motion_id("tk_2")
+            .await
+            .unwrap()
+            .is_some());
+
+        // A lost index is rebuilt from the mappings
+        keyspace
+            .delete_partition(store.motion_index.clone())
+            .unwrap();
+        drop(store);
+        let store = MappingStore::new(&keyspace, Quarantine::new(&keyspace).unwrap()).unwrap();
+        assert!(store
+            .get_mapping_by_motion_id("tk_2")
+            .await
+            .unwrap()
+            .is_some());
+
+        store.remove_mapping("personal", "issue-1").await.unwrap();
+        assert!(store
+            .get_mapping_by_motion_id("tk_2")
+            .await
+            .unwrap()
+            .is_none());
+
+        drop((store, keyspace));
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_snapshot_keeps_unpushed_edits() {
+        let written = MotionTask {
+            priority: Some("MEDIUM".to_string()),
+            duration: Some(TaskDuration::Minutes(60)),
+            ..crate::clients::testing::task("tk_1", "[CAR-1] Ship it")
+        };
+        let snapshot = MotionSnapshot::from(&written);
+        let current = MotionSnapshot::from(&MotionTask {
+            priority: Some("HIGH".to_string()),
+            duration: Some(TaskDuration::Minutes(90)),
+            ..written.clone()
+        });
+
+        // Only the pushed duration is taken, the priority edit stays visible
+        let refreshed = snapshot.with_fields_from(&current, &["duration".to_string()]);
+        assert_eq!(refreshed.duration_mins, Some(90));
+        assert_eq!(
+            refreshed.edited_fields(&current),
+            vec!["priority".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_create_intent_matches() {
+        let intent = CreateIntent {
+            identifier: "CAR-1".to_string(),
+            started_at: chrono::Utc::now(),
+        };
+        let labelled = |name: &str| MotionTask {
+            labels: Some(vec![crate::clients::motion::Label {
+                name: "linear-sync".to_string(),
+            }]),
+            ..crate::clients::testing::task("tk_1", name)
+        };
+
+        assert!(intent.matches(&labelled("[CAR-1] Ship it")));
+        assert!(!intent.matches(&labelled("[CAR-10] Ship it")));
+        assert!(!intent.matches(&labelled("Ship it CAR-1")));
+        assert!(!intent.matches(&crate::clients::testing::task("tk_1", "[CAR-1] Ship it")));
+    }
+
+    #[tokio::test]
+    async fn test_tombstones() {
+        let path = std::env::temp_dir().join(format!("linear-motion-{}", uuid::Uuid::new_v4()));
+        let keyspace = fjall::Config::new(&path).open_transactional().unwrap();
+        let store = MappingStore::new(&keyspace, Quarantine::new(&keyspace).unwrap()).unwrap();
+
+        let mut mapping = TaskMapping::new(
+            "issue-1".to_string(),
+            "tk_1".to_string(),
+            "personal".to_string(),
+        );
+        mapping.linear_issue_data = serde_json::json!({ "identifier": "CAR-1" });
+        let tombstone = |id: &str, days_ago: i64| Tombstone {
+            motion_task: crate::clients::testing::task(id, "[CAR-1] Ship it"),
+            mapping: Some(mapping.clone()),
+            reason: "Linear issue no longer assigned".to_string(),
+            deleted_at: chrono::Utc::now() - chrono::Duration::days(days_ago),
+        };
+        store.store_tombstone(&tombstone("tk_1", 1)).await.unwrap();
+        store
+            .store_tombstone(&tombstone("tk_old", 40))
+            .await
+            .unwrap();
+
+        let stored = store.get_tombstone("tk_1").await.unwrap().unwrap();
+        assert_eq!(stored.identifier().as_deref(), Some("CAR-1"));
+        assert_eq!(
+            stored.mapping.unwrap().motion_task_id.as_deref(),
+            Some("tk_1")
+        );
+        let listed: Vec<_> = store
+            .list_tombstones()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|tombstone| tombstone.motion_task_id().to_string())
+            .collect();
+        assert_eq!(listed, vec!["tk_1", "tk_old"]);
+
+        // Only tombstones past the retention period are purged
+        assert_eq!(store.purge_tombstones(30).await.unwrap(), 1);
+        assert!(store.get_tombstone("tk_old").await.unwrap().is_none());
+        assert!(store.get_tombstone("tk_1").await.unwrap().is_some());
+
+        // Relinking points the mapping and the index at the restored task
+        let mut deleted = mapping.clone();
+        deleted.motion_task_id = None;
+        deleted.deletion_held_since = Some(chrono::Utc::now());
+        store.store_mapping(deleted.clone()).await.unwrap();
+        store
+            .relink_restored(
+                deleted,
+                &crate::clients::testing::task("tk_2", "[CAR-1] Ship it"),
+            )
+            .await
+            .unwrap();
+        let relinked = store
+            .get_mapping_by_motion_id("tk_2")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(relinked.status, MappingStatus::Synced));
+        assert!(relinked.deletion_held_since.is_none());
+        assert!(relinked.motion_snapshot.is_some());
+
+        drop((store, keyspace));
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[tokio::test]
+    async fn test_deletion_held() {
+        let path = std::env::temp_dir().join(format!("linear-motion-{}", uuid::Uuid::new_v4()));
+        let keyspace = fjall::Config::new(&path).open_transactional().unwrap();
+        let store = MappingStore::new(&keyspace, Quarantine::new(&keyspace).unwrap()).unwrap();
+        store
+            .store_mapping(TaskMapping::new(
+                "issue-1".to_string(),
+                "tk_1".to_string(),
+                "personal".to_string(),
+            ))
+            .await
+            .unwrap();
+        let held_since = || async {
+            store
+                .get_mapping_by_linear_id("personal", "issue-1")
+                .await
+                .unwrap()
+                .unwrap()
+                .deletion_held_since
+        };
+
+        store
+            .set_deletion_held("personal", "issue-1", true)
+            .await
+            .unwrap();
+        let since = held_since().await.unwrap();
+
+        // Holding again keeps the time it was first held
+        store
+            .set_deletion_held("personal", "issue-1", true)
+            .await
+            .unwrap();
+        assert_eq!(held_since().await, Some(since));
+
+        store
+            .set_deletion_held("personal", "issue-1", false)
+            .await
+            .unwrap();
+        assert_eq!(held_since().await, None);
+
+        drop((store, keyspace));
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[tokio::test]
+    async fn test_deleted_downstream() {
+        let path = std::env::temp_dir().join(format!("linear-motion-{}", uuid::Uuid::new_v4()));
+        let keyspace = fjall::Config::new(&path).open_transactional().unwrap();
+        let store = MappingStore::new(&keyspace, Quarantine::new(&keyspace).unwrap()).unwrap();
+        async fn get(store: &MappingStore, id: &str) -> TaskMapping {
+            store
+                .get_mapping_by_linear_id("personal", id)
+                .await
+                .unwrap()
+                .unwrap()
         }
+
+        for (id, task) in [("issue-1", "tk_1"), ("issue-2", "tk_2")] {
+            let mut mapping =
+                TaskMapping::new(id.to_string(), task.to_string(), "personal".to_string());
+            mapping.set_synced(task.to_string());
+            store.store_mapping(mapping).await.unwrap();
+        }
+
+        store
+            .mark_deleted_downstream("personal", "issue-1", DeletionOutcome::Recreate)
+            .await
+            .unwrap();
+        let recreated = get(&store, "issue-1").await;
+        assert!(matches!(recreated.status, MappingStatus::Pending));
+        assert_eq!(recreated.motion_task_id, None);
+        assert!(store
+            .get_mapping_by_motion_id("tk_1")
+            .await
+            .unwrap()
+            .is_none());
+
+        store
+            .mark_deleted_downstream("personal", "issue-2", DeletionOutcome::Label)
+            .await
+            .unwrap();
+        let ignored = get(&store, "issue-2").await;
+        assert!(matches!(ignored.status, MappingStatus::Ignored));
+        let deletion = ignored.downstream_deletion.unwrap();
+        assert_eq!(
+            (deletion.motion_task_id.as_str(), deletion.outcome),
+            ("tk_2", DeletionOutcome::Label)
+        );
+
+        // Ignored issues can be resumed, anything else is left alone
+        assert!(!store.resume_ignored("personal", "issue-1").await.unwrap());
+        assert!(store.resume_ignored("personal", "issue-2").await.unwrap());
+        let resumed = get(&store, "issue-2").await;
+        assert!(matches!(resumed.status, MappingStatus::Pending));
+        assert!(resumed.downstream_deletion.is_none());
+
+        drop((store, keyspace));
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[tokio::test]
+    async fn test_mark_completed() {
+        let path = std::env::temp_dir().join(format!("linear-motion-{}", uuid::Uuid::new_v4()));
+        let keyspace = fjall::Config::new(&path).open_transactional().unwrap();
+        let store = MappingStore::new(&keyspace, Quarantine::new(&keyspace).unwrap()).unwrap();
+
+        store
+            .store_mapping(TaskMapping::new(
+                "issue-1".to_string(),
+                "tk_1".to_string(),
+                "personal".to_string(),
+            ))
+            .await
+            .unwrap();
+        let mut issue = crate::clients::testing::issue("issue-1", "CAR-1", "Ship it");
+        issue.state.state_type = "completed".to_string();
+        store
+            .mark_completed("personal", "issue-1", Some(&issue))
+            .await
+            .unwrap();
+
+        let completed = store
+            .get_mapping_by_linear_id("personal", "issue-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(completed.status, MappingStatus::Completed));
+        assert_eq!(
+            completed.linear_issue_data["state"]["state_type"],
+            "completed"
+        );
+        let completed_at = completed.completed_at.unwrap();
+
+        // Completing again keeps the original completion time
+        store
+            .mark_completed("personal", "issue-1", None)
+            .await
+            .unwrap();
+        let again = store
+            .get_mapping_by_linear_id("personal", "issue-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(again.completed_at, Some(completed_at));
+        assert_eq!(again.linear_issue_data["state"]["state_type"], "completed");
+
+        // Unknown issues are left alone
+        store
+            .mark_completed("personal", "issue-2", None)
+            .await
+            .unwrap();
+        assert!(store
+            .get_mapping_by_linear_id("personal", "issue-2")
+            .await
+            .unwrap()
+            .is_none());
+
+        drop((store, keyspace));
+        let _ = std::fs::remove_dir_all(&path);
     }
 }
@@ -1,12 +1,21 @@
+pub mod export;
+pub mod history;
 pub mod mapping;
+pub mod schema;
 pub mod status;
 
 use crate::Result;
-use std::path::Path;
+use fjall::{PersistMode, TxKeyspace};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use tracing::debug;
 
 pub struct SyncDatabase {
+    keyspace: TxKeyspace,
+    quarantine: schema::Quarantine,
     pub mappings: mapping::MappingStore,
     pub status: status::StatusStore,
+    pub history: history::EventLog,
 }
 
 impl SyncDatabase {
@@ -18,10 +27,22 @@ impl SyncDatabase {
             std::fs::create_dir_all(parent)?;
         }
 
-        let mappings = mapping::MappingStore::new(db_path).await?;
-        let status = status::StatusStore::new(db_path).await?;
+        // One keyspace for every store, so related records can be committed together
+        let keyspace = fjall::Config::new(db_path).open_transactional()?;
+        let quarantine = schema::Quarantine::new(&keyspace)?;
+        schema::migrate(&keyspace, &quarantine)?;
 
-        Ok(Self { mappings, status })
+        let mappings = mapping::MappingStore::new(&keyspace, quarantine.clone())?;
+        let status = status::StatusStore::new(&keyspace, quarantine.clone())?;
+        let history = history::EventLog::new(&keyspace, quarantine.clone())?;
+
+        Ok(Self {
+            keyspace,
+            quarantine,
+            mappings,
+            status,
+            history,
+        })
     }
 
     pub async fn initialize_with_default_path() -> Result<Self> {
@@ -34,11 +55,267 @@ impl SyncDatabase {
     }
 
     pub async fn flush(&self) -> Result<()> {
-        self.mappings.flush().await?;
-        self.status.flush().await?;
+        self.keyspace.persist(PersistMode::SyncAll)?;
         Ok(())
     }
+
+    /// Write every record to `writer` as versioned JSON Lines
+    pub fn export(&self, writer: impl Write) -> Result<usize> {
+        export::export(&self.keyspace, writer)
+    }
+
+    /// Restore an export, then rebuild the indexes derived from the imported mappings
+    pub fn import(
+        &self,
+        reader: impl BufRead,
+        conflict: export::ImportConflict,
+    ) -> Result<export::ImportSummary> {
+        let summary = export::import(&self.keyspace, reader, conflict)?;
+        self.mappings.rebuild_motion_index()?;
+        Ok(summary)
+    }
+
+    /// Export a consistent snapshot into a new timestamped file in `dir`
+    pub fn backup(&self, dir: &Path) -> Result<PathBuf> {
+        export::backup(&self.keyspace, dir)
+    }
+
+    /// Records that couldn't be read and were moved aside
+    pub fn quarantined_records(&self) -> Result<Vec<schema::QuarantinedRecord>> {
+        self.quarantine.list()
+    }
+
+    /// Apply the outcome of syncing one issue to its mapping, its status entry and the event
+    /// log in one commit. Both records are read inside the transaction.
+    pub async fn record_outcome(
+        &self,
+        sync_source: &str,
+        linear_issue_id: &str,
+        status_entry_id: &str,
+        update_mapping: impl FnOnce(&mut TaskMapping) -> Result<Option<history::SyncEvent>>,
+        update_entry: impl FnOnce(&mut SyncStatusEntry),
+    ) -> Result<()> {
+        let mut tx = self.keyspace.write_tx();
+        if let Some(mut mapping) = self
+            .mappings
+            .read_mapping(&tx, sync_source, linear_issue_id)?
+        {
+            let event = update_mapping(&mut mapping)?;
+            self.mappings.write_mapping(&mut tx, &mapping)?;
+            if let Some(event) = event {
+                self.history.write_event(&mut tx, &event)?;
+            }
+        }
+        if let Some(mut entry) = self.status.read_status_entry(&tx, status_entry_id)? {
+            update_entry(&mut entry);
+            self.status.write_status_entry(&mut tx, &entry)?;
+        }
+        tx.commit()?;
+
+        debug!("Recorded sync outcome for {}", linear_issue_id);
+        Ok(())
+    }
+
+    /// Mark a mapping and its status entry failed and log the error in one commit
+    pub async fn mark_failed(
+        &self,
+        sync_source: &str,
+        linear_issue_id: &str,
+        status_entry_id: &str,
+        error: String,
+    ) -> Result<()> {
+        self.record_outcome(
+            sync_source,
+            linear_issue_id,
+            status_entry_id,
+            |mapping| {
+                mapping.set_failed(error.clone());
+                Ok(Some(history::SyncEvent::new(
+                    mapping,
+                    history::SyncEventKind::Error {
+                        message: error.clone(),
+                    },
+                )))
+            },
+            |entry| entry.set_status(status::SyncStatus::Failed, Some(error.clone())),
+        )
+        .await
+    }
 }
 
 pub use mapping::{MappingStatus, TaskMapping};
 pub use status::{SyncSourceStatus, SyncStatus, SyncStatusEntry};
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Error;
+
+    async fn open() -> (PathBuf, SyncDatabase, SyncStatusEntry) {
+        let path = std::env::temp_dir().join(format!("linear-motion-{}", uuid::Uuid::new_v4()));
+        let database = SyncDatabase::new(&path).await.unwrap();
+
+        let mut mapping = TaskMapping::new(
+            "issue-1".to_string(),
+            "tk_1".to_string(),
+            "personal".to_string(),
+        );
+        mapping.linear_issue_data = serde_json::json!({ "identifier": "ENG-1" });
+        database.mappings.store_mapping(mapping).await.unwrap();
+        let entry = database
+            .status
+            .create_status_entry("personal".to_string(), "issue-1".to_string())
+            .await
+            .unwrap();
+
+        (path, database, entry)
+    }
+
+    #[tokio::test]
+    async fn test_record_outcome() {
+        let (path, database, entry) = open().await;
+
+        // A failed update keeps the mapping synced and records the error on both records
+        database
+            .record_outcome(
+                "personal",
+                "issue-1",
+                &entry.id,
+                |mapping| {
+                    mapping.set_update_failed("rate limited".to_string());
+                    Ok(Some(history::SyncEvent::new(
+                        mapping,
+                        history::SyncEventKind::Error {
+                            message: "rate limited".to_string(),
+                        },
+                    )))
+                },
+                |entry| entry.set_status(SyncStatus::Failed, Some("rate limited".to_string())),
+            )
+            .await
+            .unwrap();
+
+        let mapping = database
+            .mappings
+            .get_mapping_by_linear_id("personal", "issue-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(mapping.status, MappingStatus::Synced));
+        assert_eq!(mapping.sync_error.as_deref(), Some("rate limited"));
+        assert!(mapping.last_sync_attempt.is_some());
+        let stored = database
+            .status
+            .get_status_entry(&entry.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(stored.status, SyncStatus::Failed));
+        assert_eq!(
+            database
+                .history
+                .events_for_identifier("ENG-1")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // A failing mapping change leaves every record as it was
+        let result = database
+            .record_outcome(
+                "personal",
+                "issue-1",
+                &entry.id,
+                |mapping| {
+                    mapping.set_synced("tk_2".to_string());
+                    Err(Error::Validation("unreadable issue".to_string()))
+                },
+                |entry| entry.set_completed("tk_2".to_string()),
+            )
+            .await;
+        assert!(matches!(result, Err(Error::Validation(_))));
+        let mapping = database
+            .mappings
+            .get_mapping_by_linear_id("personal", "issue-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(mapping.motion_task_id.as_deref(), Some("tk_1"));
+        let stored = database
+            .status
+            .get_status_entry(&entry.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(stored.status, SyncStatus::Failed));
+
+        // A failed create marks the mapping failed
+
+        database
+            .mark_failed("personal", "issue-1", &entry.id, "timeout".to_string())
+            .await
+            .unwrap();
+        let mapping = database
+            .mappings
+            .get_mapping_by_linear_id("personal", "issue-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(mapping.status, MappingStatus::Failed));
+
+        // A created task syncs the mapping, indexes the task and completes the entry
+        database
+            .record_outcome(
+                "personal",
+                "issue-1",
+                &entry.id,
+                |mapping| {
+                    mapping.set_synced("tk_2".to_string());
+                    Ok(Some(history::SyncEvent::new(
+                        mapping,
+                        history::SyncEventKind::Created,
+                    )))
+                },
+                |entry| entry.set_completed("tk_2".to_string()),
+            )
+            .await
+            .unwrap();
+        let mapping = database
+            .mappings
+            .get_mapping_by_linear_id("personal", "issue-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(mapping.status, MappingStatus::Synced));
+        assert_eq!(mapping.sync_error, None);
+        assert_eq!(
+            database
+                .mappings
+                .get_mapping_by_motion_id("tk_2")
+                .await
+                .unwrap()
+                .map(|mapping| mapping.linear_issue_id),
+            Some("issue-1".to_string())
+        );
+        let stored = database
+            .status
+            .get_status_entry(&entry.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(stored.status, SyncStatus::Completed));
+        assert_eq!(stored.motion_task_id.as_deref(), Some("tk_2"));
+
+        let events = database
+            .history
+            .events_for_issue("personal", "issue-1")
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[2].kind, history::SyncEventKind::Created);
+
+        drop(database);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}
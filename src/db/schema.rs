@@ -0,0 +1,260 @@
+use crate::db::history::SyncEvent;
+use crate::db::mapping::{TaskMapping, Tombstone};
+use crate::db::status::{SyncSourceStatus, SyncStatusEntry};
+use crate::{Error, Result};
+use fjall::{TxKeyspace, TxPartitionHandle, WriteTransaction};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
+/// The record layout this build reads and writes
+pub const SCHEMA_VERSION: u32 = 1;
+
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// An upgrade of stored records to a new schema version
+struct Migration {
+    version: u32,
+    description: &'static str,
+    /// Rewrites one JSON record of the named partition in place
+    upgrade: fn(partition: &str, record: &mut Value),
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "Version records and store them with every current field",
+    upgrade: |_, _| {},
+}];
+
+/// Parses a record as its stored type and serializes it again
+type Canonicalize = fn(Value) -> serde_json::Result<Vec<u8>>;
+
+/// The JSON partitions and how to check a record still reads as its type
+const RECORD_PARTITIONS: &[(&str, Canonicalize)] = &[
+    ("task_mappings", canonical::<TaskMapping>),
+    ("sync_statuses", canonical::<SyncStatusEntry>),
+    ("source_stats", canonical::<SyncSourceStatus>),
+    ("tombstones", canonical::<Tombstone>),
+    ("sync_events", canonical::<SyncEvent>),
+];
+
+/// The partitions holding JSON records, in the order they're exported
+pub fn record_partitions() -> impl Iterator<Item = &'static str> {
+    RECORD_PARTITIONS.iter().map(|(name, _)| *name)
+}
+
+/// Upgrade one record of `partition` written at schema `version` to the current layout
+pub fn upgrade_record(partition: &str, version: u32, mut record: Value) -> Result<Vec<u8>> {
+    let canonicalize = RECORD_PARTITIONS
+        .iter()
+        .find(|(name, _)| *name == partition)
+        .map(|(_, canonicalize)| canonicalize)
+        .ok_or_else(|| Error::Schema(format!("unknown partition {}", partition)))?;
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > version) {
+        (migration.upgrade)(partition, &mut record);
+    }
+    Ok(canonicalize(record)?)
+}
+
+/// Parse a record as `T` and serialize it again, filling in defaulted fields
+fn canonical<T: DeserializeOwned + Serialize>(value: Value) -> serde_json::Result<Vec<u8>> {
+    let record: T = serde_json::from_value(value)?;
+    serde_json::to_vec(&record)
+}
+
+/// A record that could not be read, moved aside instead of failing the scan it was found in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantinedRecord {
+    pub partition: String,
+    pub key: String,
+    /// The raw stored bytes, lossily decoded as UTF-8
+    pub value: String,
+    pub error: String,
+    pub quarantined_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Holds unreadable records so the rest of the database stays usable
+#[derive(Clone)]
+pub struct Quarantine {
+    keyspace: TxKeyspace,
+    records: TxPartitionHandle,
+}
+
+impl Quarantine {
+    pub fn new(keyspace: &TxKeyspace) -> Result<Self> {
+        let records =
+            keyspace.open_partition("quarantine", fjall::PartitionCreateOptions::default())?;
+
+        Ok(Self {
+            keyspace: keyspace.clone(),
+            records,
+        })
+    }
+
+    /// Read a stored record, quarantining it if it doesn't deserialize
+    pub fn decode<T: DeserializeOwned>(
+        &self,
+        partition: &TxPartitionHandle,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<Option<T>> {
+        match serde_json::from_slice(value) {
+            Ok(record) => Ok(Some(record)),
+            Err(e) => {
+                let mut tx = self.keyspace.write_tx();
+                self.isolate(&mut tx, partition, key, value, &e.to_string())?;
+                tx.commit()?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Move a record from `partition` into quarantine as part of `tx`
+    fn isolate(
+        &self,
+        tx: &mut WriteTransaction,
+        partition: &TxPartitionHandle,
+        key: &[u8],
+        value: &[u8],
+        error: &str,
+    ) -> Result<()> {
+        let partition_name = partition.inner().name.to_string();
+        let record = QuarantinedRecord {
+            partition: partition_name.clone(),
+            key: String::from_utf8_lossy(key).to_string(),
+            value: String::from_utf8_lossy(value).to_string(),
+            error: error.to_string(),
+            quarantined_at: chrono::Utc::now(),
+        };
+        warn!(
+            "Quarantining unreadable record {}/{}: {}",
+            record.partition, record.key, record.error
+        );
+
+        let quarantine_key = format!("{}/{}", record.partition, record.key);
+        tx.insert(&self.records, quarantine_key, serde_json::to_vec(&record)?);
+        tx.remove(partition, key);
+        Ok(())
+    }
+
+    pub fn list(&self) -> Result<Vec<QuarantinedRecord>> {
+        let mut records = Vec::new();
+
+        for item in self.keyspace.read_tx().iter(&self.records) {
+            let (_, value) = item?;
+            records.push(serde_json::from_slice(&value)?);
+        }
+
+        Ok(records)
+    }
+}
+
+/// Bring the stored records up to `SCHEMA_VERSION`, quarantining any that can't be upgraded
+pub fn migrate(keyspace: &TxKeyspace, quarantine: &Quarantine) -> Result<()> {
+    let metadata = keyspace.open_partition("metadata", fjall::PartitionCreateOptions::default())?;
+    let version = match metadata.get(SCHEMA_VERSION_KEY)? {
+        Some(value) => serde_json::from_slice::<u32>(&value)?,
+        // Databases from before versioning
+        None => 0,
+    };
+
+    if version > SCHEMA_VERSION {
+        return Err(Error::Schema(format!(
+            "database schema v{} is newer than the supported v{}, upgrade linear-motion",
+            version, SCHEMA_VERSION
+        )));
+    }
+    if version == SCHEMA_VERSION {
+        debug!("database schema is up to date (v{})", version);
+        return Ok(());
+    }
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > version) {
+        info!(
+            "Migrating database to schema v{}: {}",
+            migration.version, migration.description
+        );
+    }
+
+    // Upgrade everything in one commit so a crash leaves the old version intact
+    let read = keyspace.read_tx();
+    let mut tx = keyspace.write_tx();
+    let mut quarantined = 0;
+    for name in record_partitions() {
+        let partition = keyspace.open_partition(name, fjall::PartitionCreateOptions::default())?;
+
+        for item in read.iter(&partition) {
+            let (key, value) = item?;
+            let upgraded = serde_json::from_slice::<Value>(&value)
+                .map_err(Error::from)
+                .and_then(|record| upgrade_record(name, version, record));
+
+            match upgraded {
+                Ok(upgraded) => tx.insert(&partition, key, upgraded),
+                Err(e) => {
+                    quarantine.isolate(&mut tx, &partition, &key, &value, &e.to_string())?;
+                    quarantined += 1;
+                }
+            }
+        }
+    }
+    tx.insert(
+        &metadata,
+        SCHEMA_VERSION_KEY,
+        serde_json::to_vec(&SCHEMA_VERSION)?,
+    );
+    tx.commit()?;
+
+    info!(
+        "Database migrated from schema v{} to v{} ({} records quarantined)",
+        version, SCHEMA_VERSION, quarantined
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_migrate_quarantines_unreadable_records() {
+        let path = std::env::temp_dir().join(format!("linear-motion-{}", uuid::Uuid::new_v4()));
+        let keyspace = fjall::Config::new(&path).open_transactional().unwrap();
+        let quarantine = Quarantine::new(&keyspace).unwrap();
+
+        // An unversioned database with one good and one corrupt status entry
+        let statuses = keyspace
+            .open_partition("sync_statuses", fjall::PartitionCreateOptions::default())
+            .unwrap();
+        let entry = SyncStatusEntry::new("personal".to_string(), "issue-1".to_string());
+        statuses
+            .insert(&entry.id, serde_json::to_vec(&entry).unwrap())
+            .unwrap();
+        statuses.insert("broken", b"{\"id\": 1").unwrap();
+
+        migrate(&keyspace, &quarantine).unwrap();
+
+        assert!(statuses.get(&entry.id).unwrap().is_some());
+        assert!(statuses.get("broken").unwrap().is_none());
+        let records = quarantine.list().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].partition, "sync_statuses");
+        assert_eq!(records[0].key, "broken");
+
+        let metadata = keyspace
+            .open_partition("metadata", fjall::PartitionCreateOptions::default())
+            .unwrap();
+        metadata
+            .insert(SCHEMA_VERSION_KEY, serde_json::to_vec(&99).unwrap())
+            .unwrap();
+        assert!(matches!(
+            migrate(&keyspace, &quarantine),
+            Err(Error::Schema(_))
+        ));
+
+        drop((statuses, metadata, quarantine, keyspace));
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}
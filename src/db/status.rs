@@ -1,7 +1,7 @@
+use crate::db::schema::Quarantine;
 use crate::Result;
-use fjall::{Keyspace, PartitionHandle, PersistMode};
+use fjall::{TxKeyspace, TxPartitionHandle, WriteTransaction};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
@@ -35,17 +35,19 @@ pub struct SyncSourceStatus {
     pub successful_syncs: u64,
     pub failed_syncs: u64,
     pub errors: Vec<String>,
+    #[serde(default)]
+    pub last_drift_check: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 pub struct StatusStore {
-    keyspace: Keyspace,
-    statuses: PartitionHandle,
-    source_stats: PartitionHandle,
+    keyspace: TxKeyspace,
+    statuses: TxPartitionHandle,
+    source_stats: TxPartitionHandle,
+    quarantine: Quarantine,
 }
 
 impl StatusStore {
-    pub async fn new<P: AsRef<Path>>(db_path: P) -> Result<Self> {
-        let keyspace = fjall::Config::new(db_path).open()?;
+    pub fn new(keyspace: &TxKeyspace, quarantine: Quarantine) -> Result<Self> {
         let statuses =
             keyspace.open_partition("sync_statuses", fjall::PartitionCreateOptions::default())?;
         let source_stats =
@@ -54,12 +56,36 @@ impl StatusStore {
         debug!("status tracking store initialized");
 
         Ok(Self {
-            keyspace,
+            keyspace: keyspace.clone(),
             statuses,
             source_stats,
+            quarantine,
         })
     }
 
+    /// Write a status entry as part of `tx`
+    pub(crate) fn write_status_entry(
+        &self,
+        tx: &mut WriteTransaction,
+        entry: &SyncStatusEntry,
+    ) -> Result<()> {
+        let value = serde_json::to_vec(entry)?;
+        tx.insert(&self.statuses, entry.id.as_str(), value);
+        Ok(())
+    }
+
+    /// Read a status entry as part of `tx`, so it can be changed and written back in the same commit
+    pub(crate) fn read_status_entry(
+        &self,
+        tx: &WriteTransaction,
+        id: &str,
+    ) -> Result<Option<SyncStatusEntry>> {
+        match tx.get(&self.statuses, id)? {
+            Some(value) => Ok(Some(serde_json::from_slice(&value)?)),
+            None => Ok(None),
+        }
+    }
+
     pub async fn create_status_entry(
         &self,
         sync_source: String,
@@ -71,10 +97,9 @@ impl StatusStore {
     }
 
     pub async fn store_status_entry(&self, entry: &SyncStatusEntry) -> Result<()> {
-        let key = &entry.id;
-        let value = serde_json::to_vec(entry)?;
-
-        self.statuses.insert(key, &value)?;
+        let mut tx = self.keyspace.write_tx();
+        self.write_status_entry(&mut tx, entry)?;
+        tx.commit()?;
         debug!(
             "Stored status entry: {} ({})",
             entry.id, entry.linear_issue_id
@@ -85,10 +110,9 @@ impl StatusStore {
 
     pub async fn get_status_entry(&self, id: &str) -> Result<Option<SyncStatusEntry>> {
         match self.statuses.get(id)? {
-            Some(value) => {
-                let entry: SyncStatusEntry = serde_json::from_slice(&value)?;
-                Ok(Some(entry))
-            }
+            Some(value) => self
+                .quarantine
+                .decode(&self.statuses, id.as_bytes(), &value),
             None => Ok(None),
         }
     }
@@ -100,14 +124,7 @@ impl StatusStore {
         error_message: Option<String>,
     ) -> Result<()> {
         if let Some(mut entry) = self.get_status_entry(id).await? {
-            entry.status = status;
-            entry.error_message = error_message.clone();
-            entry.last_sync_attempt = chrono::Utc::now();
-            entry.updated_at = chrono::Utc::now();
-
-            if error_message.is_some() {
-                entry.retry_count += 1;
-            }
+            entry.set_status(status, error_message);
 
             self.store_status_entry(&entry).await?;
             debug!("Updated status for {}: {:?}", id, entry.status);
@@ -120,11 +137,7 @@ impl StatusStore {
 
     pub async fn mark_completed(&self, id: &str, motion_task_id: String) -> Result<()> {
         if let Some(mut entry) = self.get_status_entry(id).await? {
-            entry.status = SyncStatus::Completed;
-            entry.motion_task_id = Some(motion_task_id);
-            entry.last_sync_attempt = chrono::Utc::now();
-            entry.updated_at = chrono::Utc::now();
-            entry.error_message = None;
+            entry.set_completed(motion_task_id);
 
             self.store_status_entry(&entry).await?;
             debug!("Marked {} as completed", id);
@@ -141,9 +154,14 @@ impl StatusStore {
     pub async fn list_statuses_by_source(&self, sync_source: &str) -> Result<Vec<SyncStatusEntry>> {
         let mut entries = Vec::new();
 
-        for item in self.statuses.iter() {
-            let (_, value) = item?;
-            let entry: SyncStatusEntry = serde_json::from_slice(&value)?;
+        for item in self.keyspace.read_tx().iter(&self.statuses) {
+            let (key, value) = item?;
+            let Some(entry) =
+                self.quarantine
+                    .decode::<SyncStatusEntry>(&self.statuses, &key, &value)?
+            else {
+                continue;
+            };
 
             if entry.sync_source == sync_source {
                 entries.push(entry);
@@ -161,9 +179,14 @@ impl StatusStore {
     pub async fn list_failed_entries(&self) -> Result<Vec<SyncStatusEntry>> {
         let mut failed_entries = Vec::new();
 
-        for item in self.statuses.iter() {
-            let (_, value) = item?;
-            let entry: SyncStatusEntry = serde_json::from_slice(&value)?;
+        for item in self.keyspace.read_tx().iter(&self.statuses) {
+            let (key, value) = item?;
+            let Some(entry) =
+                self.quarantine
+                    .decode::<SyncStatusEntry>(&self.statuses, &key, &value)?
+            else {
+                continue;
+            };
 
             if matches!(entry.status, SyncStatus::Failed) {
                 failed_entries.push(entry);
@@ -177,8 +200,8 @@ impl StatusStore {
     pub async fn get_source_status(&self, source_name: &str) -> Result<Option<SyncSourceStatus>> {
         match self.source_stats.get(source_name)? {
             Some(value) => {
-                let status: SyncSourceStatus = serde_json::from_slice(&value)?;
-                Ok(Some(status))
+                self.quarantine
+                    .decode(&self.source_stats, source_name.as_bytes(), &value)
             }
             None => Ok(None),
         }
@@ -222,13 +245,29 @@ impl StatusStore {
         Ok(())
     }
 
+    pub async fn record_drift_check(&self, source_name: &str) -> Result<()> {
+        let mut status = self
+            .get_source_status(source_name)
+            .await?
+            .unwrap_or_else(|| SyncSourceStatus::new(source_name.to_string()));
+
+        status.last_drift_check = Some(chrono::Utc::now());
+
+        let value = serde_json::to_vec(&status)?;
+        self.source_stats.insert(source_name, &value)?;
+
+        debug!("Recorded drift check for source {}", source_name);
+        Ok(())
+    }
+
     pub async fn list_all_source_stats(&self) -> Result<Vec<SyncSourceStatus>> {
         let mut stats = Vec::new();
 
-        for item in self.source_stats.iter() {
-            let (_, value) = item?;
-            let status: SyncSourceStatus = serde_json::from_slice(&value)?;
-            stats.push(status);
+        for item in self.keyspace.read_tx().iter(&self.source_stats) {
+            let (key, value) = item?;
+            if let Some(status) = self.quarantine.decode(&self.source_stats, &key, &value)? {
+                stats.push(status);
+            }
         }
 
         Ok(stats)
@@ -240,9 +279,14 @@ impl StatusStore {
 
         let mut keys_to_delete = Vec::new();
 
-        for item in self.statuses.iter() {
+        for item in self.keyspace.read_tx().iter(&self.statuses) {
             let (key, value) = item?;
-            let entry: SyncStatusEntry = serde_json::from_slice(&value)?;
+            let Some(entry) =
+                self.quarantine
+                    .decode::<SyncStatusEntry>(&self.statuses, &key, &value)?
+            else {
+                continue;
+            };
 
             if entry.created_at < cutoff && matches!(entry.status, SyncStatus::Completed) {
                 keys_to_delete.push(key.to_vec());
@@ -260,14 +304,27 @@ impl StatusStore {
 
         Ok(deleted_count)
     }
-
-    pub async fn flush(&self) -> Result<()> {
-        self.keyspace.persist(PersistMode::SyncAll)?;
-        Ok(())
-    }
 }
 
 impl SyncStatusEntry {
+    pub fn set_status(&mut self, status: SyncStatus, error_message: Option<String>) {
+        if error_message.is_some() {
+            self.retry_count += 1;
+        }
+        self.status = status;
+        self.error_message = error_message;
+        self.last_sync_attempt = chrono::Utc::now();
+        self.updated_at = chrono::Utc::now();
+    }
+
+    pub fn set_completed(&mut self, motion_task_id: String) {
+        self.status = SyncStatus::Completed;
+        self.motion_task_id = Some(motion_task_id);
+        self.last_sync_attempt = chrono::Utc::now();
+        self.updated_at = chrono::Utc::now();
+        self.error_message = None;
+    }
+
     pub fn new(sync_source: String, linear_issue_id: String) -> Self {
         let now = chrono::Utc::now();
 
@@ -295,6 +352,7 @@ impl SyncSourceStatus {
             successful_syncs: 0,
             failed_syncs: 0,
             errors: Vec::new(),
+            last_drift_check: None,
         }
     }
 }
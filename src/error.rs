@@ -10,6 +10,9 @@ pub enum Error {
     #[error("Database error: {0}")]
     Database(#[from] fjall::Error),
 
+    #[error("Database schema error: {0}")]
+    Schema(String),
+
     #[error("HTTP client error: {0}")]
     Http(#[from] reqwest::Error),
 
@@ -25,6 +28,9 @@ pub enum Error {
     #[error("Motion API error: {message}")]
     MotionApi { message: String },
 
+    #[error("Not found: {0}")]
+    NotFound(String),
+
     #[error("Linear API error: {message}")]
     LinearApi { message: String },
 
@@ -1,5 +1,5 @@
 use clap::Parser;
-use linear_motion::cli::commands::{Cli, Commands};
+use linear_motion::cli::commands::{Cli, Commands, DbCommands};
 use linear_motion::{Error, Result};
 use tracing::info;
 
@@ -28,11 +28,21 @@ async fn main() -> Result<()> {
             watch,
             pid_file,
             force,
+            adopt,
+            confirm_deletions,
         } => {
-            handle_sync(cli.config.as_deref(), watch, &pid_file, force).await?;
+            handle_sync(
+                cli.config.as_deref(),
+                watch,
+                &pid_file,
+                force,
+                adopt,
+                confirm_deletions,
+            )
+            .await?;
         }
         Commands::Status => {
-            handle_status().await?;
+            handle_status(cli.config.as_deref()).await?;
         }
         Commands::Stop => {
             handle_stop().await?;
@@ -41,12 +51,38 @@ async fn main() -> Result<()> {
             let task_id = if current { None } else { id.as_deref() };
             handle_complete(cli.config.as_deref(), task_id).await?;
         }
-        Commands::Tasks { waybar } => {
-            handle_tasks(cli.config.as_deref(), waybar).await?;
+        Commands::Tasks { waybar, at_risk } => {
+            handle_tasks(cli.config.as_deref(), waybar, at_risk).await?;
+        }
+        Commands::Drift {
+            repair,
+            source,
+            json,
+        } => {
+            handle_drift(cli.config.as_deref(), repair, source.as_deref(), json).await?;
+        }
+        Commands::Reconcile {
+            dry_run,
+            delete,
+            confirm_deletions,
+        } => {
+            handle_reconcile(cli.config.as_deref(), dry_run, delete, confirm_deletions).await?;
+        }
+        Commands::Restore { target } => {
+            handle_restore(cli.config.as_deref(), target.as_deref()).await?;
+        }
+        Commands::Resume { identifier } => {
+            handle_resume(cli.config.as_deref(), &identifier).await?;
+        }
+        Commands::History { identifier, json } => {
+            handle_history(cli.config.as_deref(), &identifier, json).await?;
         }
         Commands::List { verbose, source } => {
             handle_list(cli.config.as_deref(), verbose, source.as_deref()).await?;
         }
+        Commands::Db { command } => {
+            handle_db(cli.config.as_deref(), command).await?;
+        }
     }
 
     Ok(())
@@ -54,7 +90,10 @@ async fn main() -> Result<()> {
 
 async fn handle_init(output: Option<&str>, force: bool) -> Result<()> {
     use linear_motion::config::{
-        AppConfig, ConfigLoader, ScheduleOverride, SyncRules, SyncSource, TimeEstimateStrategy,
+        AppConfig, CompletionAction, ConfigLoader, ConflictPolicy, CycleRules,
+        DefaultDeadlinePolicy, DeletedTaskPolicy, DeletionGuard, DriftCheck, PriorityMap,
+        ReverseSyncRules, ScheduleFeedback, ScheduleOverride, StatusMap, SyncRules, SyncSource,
+        TerminalStateRules, TimeEstimateStrategy,
     };
     use std::collections::HashMap;
     use std::fs;
@@ -93,6 +132,7 @@ async fn handle_init(output: Option<&str>, force: bool) -> Result<()> {
 
     let time_estimate_strategy = TimeEstimateStrategy {
         fibonacci: Some(fibonacci_mappings),
+        exponential: None,
         tshirt: Some(tshirt_mappings),
         linear: None,
         points: None,
@@ -103,6 +143,30 @@ async fn handle_init(output: Option<&str>, force: bool) -> Result<()> {
         default_task_duration_mins: 60,
         completed_linear_tag: "motioned".to_string(),
         time_estimate_strategy: time_estimate_strategy.clone(),
+        priority_map: PriorityMap::default(),
+        due_time: "23:59".to_string(),
+        default_deadline: vec![
+            DefaultDeadlinePolicy::CycleEnd,
+            DefaultDeadlinePolicy::WorkingDays(5),
+        ],
+        cycles: CycleRules::default(),
+        status_map: StatusMap {
+            by_type: HashMap::from([
+                ("unstarted".to_string(), "Todo".to_string()),
+                ("started".to_string(), "In Progress".to_string()),
+            ]),
+            by_name: HashMap::new(),
+        },
+        terminal_states: TerminalStateRules::default(),
+        completion_action: CompletionAction::default(),
+        schedule_feedback: ScheduleFeedback::default(),
+        at_risk_label: None,
+        reverse_sync: ReverseSyncRules::default(),
+        conflict_policy: ConflictPolicy::default(),
+        deleted_task_policy: DeletedTaskPolicy::default(),
+        deleted_task_label: "deleted-in-motion".to_string(),
+        drift_check: DriftCheck::default(),
+        deletion_guard: DeletionGuard::default(),
     };
 
     let sync_source = SyncSource {
@@ -134,6 +198,8 @@ async fn handle_init(output: Option<&str>, force: bool) -> Result<()> {
         database_path: None,
         polling_interval_seconds: 300,
         schedule_overrides: Some(vec![schedule_override]),
+        timezone: None,
+        tombstone_retention_days: 30,
     };
 
     // Serialize to pretty JSON
@@ -158,6 +224,8 @@ async fn handle_sync(
     watch: bool,
     pid_file: &str,
     force: bool,
+    adopt: bool,
+    confirm_deletions: bool,
 ) -> Result<()> {
     use linear_motion::config::ConfigLoader;
     use linear_motion::sync::orchestrator::SyncOrchestrator;
@@ -177,6 +245,12 @@ async fn handle_sync(
         config.sync_sources.len()
     );
 
+    if adopt {
+        let orchestrator = SyncOrchestrator::new(&config).await?;
+        adopt_existing_tasks(&orchestrator, &config).await?;
+        orchestrator.database.flush().await?;
+    }
+
     if watch {
         info!("Starting daemon mode");
 
@@ -196,7 +270,10 @@ async fn handle_sync(
 
         // Initialize and run sync orchestrator
         let orchestrator = SyncOrchestrator::new(&config).await?;
-        match orchestrator.run_full_sync(&config, force).await {
+        match orchestrator
+            .run_full_sync(&config, force, confirm_deletions)
+            .await
+        {
             Ok(()) => {
                 println!("✅ Sync completed successfully!");
             }
@@ -210,10 +287,320 @@ async fn handle_sync(
     Ok(())
 }
 
-async fn handle_status() -> Result<()> {
-    // TODO: Implement IPC client to query daemon
-    println!("📊 Status functionality not yet implemented");
-    println!("⚠️  Will query daemon via IPC when implemented");
+/// Propose links to existing Motion tasks and adopt the ones the user accepts
+async fn adopt_existing_tasks(
+    orchestrator: &linear_motion::sync::orchestrator::SyncOrchestrator,
+    config: &linear_motion::config::AppConfig,
+) -> Result<()> {
+    use linear_motion::sync::adopt::MatchReason;
+    use std::io::Write;
+
+    let candidates = orchestrator.propose_adoptions(config).await?;
+    if candidates.is_empty() {
+        println!("🔗 No existing Motion tasks match unsynced Linear issues");
+        return Ok(());
+    }
+
+    println!("🔗 Proposed Links ({} total)", candidates.len());
+    let mut accept_all = false;
+    for candidate in &candidates {
+        let reason = match candidate.reason {
+            MatchReason::Identifier => "identifier in name".to_string(),
+            MatchReason::Title { similarity } => {
+                format!("{:.0}% similar title", similarity * 100.0)
+            }
+        };
+        println!(
+            "  {} - {}  <->  {} ({})",
+            candidate.issue.identifier, candidate.issue.title, candidate.task.name, reason
+        );
+
+        if !accept_all {
+            print!("    Adopt this task? [y/N/a(ll)/q(uit)] ");
+            std::io::stdout().flush()?;
+            let mut answer = String::new();
+            std::io::stdin().read_line(&mut answer)?;
+            match answer.trim().to_lowercase().as_str() {
+                "y" | "yes" => {}
+                "a" | "all" => accept_all = true,
+                "q" | "quit" => break,
+                _ => continue,
+            }
+        }
+
+        if let Err(e) = orchestrator.adopt(candidate).await {
+            println!("    ❌ Failed to adopt: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_status(config_path: Option<&str>) -> Result<()> {
+    use linear_motion::config::ConfigLoader;
+    use linear_motion::db::SyncDatabase;
+
+    let config_path = match config_path {
+        Some(path) => path.to_string(),
+        None => ConfigLoader::get_default_config_path()?
+            .to_string_lossy()
+            .to_string(),
+    };
+    let config = ConfigLoader::load_from_file(&config_path).await?;
+    let database = SyncDatabase::new(config.database_path()).await?;
+
+    let mappings = database.mappings.list_all_mappings().await?;
+    print_at_risk_report(&mappings);
+
+    let held = mappings
+        .iter()
+        .filter(|mapping| mapping.deletion_held_since.is_some())
+        .count();
+    if held > 0 {
+        println!(
+            "🛑 {} Motion task deletions held by the deletion guard, run `sync --confirm-deletions` to delete them",
+            held
+        );
+    }
+
+    let quarantined = database.quarantined_records()?;
+    if !quarantined.is_empty() {
+        println!(
+            "☣️  {} unreadable database records quarantined and skipped:",
+            quarantined.len()
+        );
+        for record in &quarantined {
+            println!("   {}/{}: {}", record.partition, record.key, record.error);
+        }
+    }
+    Ok(())
+}
+
+/// Print the tracked issues Motion can't finish before their deadline
+fn print_at_risk_report(mappings: &[linear_motion::db::mapping::TaskMapping]) {
+    let at_risk: Vec<_> = mappings
+        .iter()
+        .filter_map(|mapping| mapping.at_risk.as_ref().map(|at_risk| (mapping, at_risk)))
+        .collect();
+
+    if at_risk.is_empty() {
+        println!("✅ No at-risk issues");
+        return;
+    }
+
+    println!("⚠️  At-Risk Issues ({} total)", at_risk.len());
+    for (mapping, at_risk) in at_risk {
+        let identifier = serde_json::from_value::<linear_motion::clients::linear::LinearIssue>(
+            mapping.linear_issue_data.clone(),
+        )
+        .map(|issue| format!("{} - {}", issue.identifier, issue.title))
+        .unwrap_or_else(|_| mapping.linear_issue_id.clone());
+
+        println!("  ⚠️  {}", identifier);
+        println!("    Reason: {}", at_risk.reason);
+        println!(
+            "    Since: {}",
+            at_risk.since.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+    }
+    println!();
+}
+
+async fn handle_drift(
+    config_path: Option<&str>,
+    repair: bool,
+    source: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    use linear_motion::config::{ConfigLoader, DriftMode};
+    use linear_motion::sync::orchestrator::SyncOrchestrator;
+
+    let config_path = match config_path {
+        Some(path) => path.to_string(),
+        None => ConfigLoader::get_default_config_path()?
+            .to_string_lossy()
+            .to_string(),
+    };
+    let config = ConfigLoader::load_from_file(&config_path).await?;
+    let orchestrator = SyncOrchestrator::new(&config).await?;
+
+    let mode = if repair {
+        DriftMode::Repair
+    } else {
+        DriftMode::Report
+    };
+    let reports = orchestrator
+        .check_drift(&config, None, Some(mode), source)
+        .await?;
+    orchestrator.database.flush().await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+        return Ok(());
+    }
+
+    if reports.is_empty() {
+        println!("✅ No drift between Motion and the stored state");
+        return Ok(());
+    }
+
+    println!("🔀 Drifted Tasks ({} total)", reports.len());
+    for report in &reports {
+        let icon = if report.repaired { "🔧" } else { "🔀" };
+        println!(
+            "  {} {} ({}) -> {}",
+            icon, report.identifier, report.sync_source, report.motion_task_id
+        );
+        for drift in &report.drift {
+            println!("    {}", drift);
+        }
+    }
+    if !repair {
+        println!();
+        println!("Run `drift --repair` to write the expected values back to Motion");
+    }
+
+    Ok(())
+}
+
+async fn handle_reconcile(
+    config_path: Option<&str>,
+    dry_run: bool,
+    delete: bool,
+    confirm_deletions: bool,
+) -> Result<()> {
+    use linear_motion::config::ConfigLoader;
+    use linear_motion::sync::orchestrator::SyncOrchestrator;
+    use linear_motion::sync::reconcile::ReconcileAction;
+
+    let config_path = match config_path {
+        Some(path) => path.to_string(),
+        None => ConfigLoader::get_default_config_path()?
+            .to_string_lossy()
+            .to_string(),
+    };
+    let config = ConfigLoader::load_from_file(&config_path).await?;
+    let orchestrator = SyncOrchestrator::new(&config).await?;
+
+    let entries = orchestrator
+        .reconcile(&config, dry_run, delete, confirm_deletions)
+        .await?;
+
+    if dry_run {
+        println!("🔍 Dry run, nothing was changed");
+    }
+    for action in [
+        ReconcileAction::Adopted,
+        ReconcileAction::Duplicate,
+        ReconcileAction::Orphaned,
+    ] {
+        let matching: Vec<_> = entries.iter().filter(|e| e.action == action).collect();
+        if matching.is_empty() {
+            continue;
+        }
+
+        let (icon, title) = match action {
+            ReconcileAction::Adopted => ("🔗", "Adopted"),
+            ReconcileAction::Duplicate => ("👥", "Duplicates"),
+            ReconcileAction::Orphaned => ("👻", "Orphaned"),
+            ReconcileAction::Mapped => continue,
+        };
+        println!("{} {} ({} total)", icon, title, matching.len());
+        for entry in matching {
+            let note = match (entry.action, entry.applied) {
+                (ReconcileAction::Adopted, true) => "",
+                (_, true) => " (deleted)",
+                (_, false) => " (not changed)",
+            };
+            println!("  {} -> {}{}", entry.task_name, entry.motion_task_id, note);
+        }
+    }
+
+    let mapped = entries
+        .iter()
+        .filter(|e| e.action == ReconcileAction::Mapped)
+        .count();
+    println!("✅ {} tasks already mapped", mapped);
+    if !delete
+        && entries.iter().any(|e| {
+            matches!(
+                e.action,
+                ReconcileAction::Duplicate | ReconcileAction::Orphaned
+            )
+        })
+    {
+        println!("Run `reconcile --delete` to remove duplicate and orphaned tasks");
+    }
+
+    Ok(())
+}
+
+async fn handle_restore(config_path: Option<&str>, target: Option<&str>) -> Result<()> {
+    use linear_motion::config::ConfigLoader;
+    use linear_motion::sync::orchestrator::SyncOrchestrator;
+
+    let config_path = match config_path {
+        Some(path) => path.to_string(),
+        None => ConfigLoader::get_default_config_path()?
+            .to_string_lossy()
+            .to_string(),
+    };
+    let config = ConfigLoader::load_from_file(&config_path).await?;
+    let orchestrator = SyncOrchestrator::new(&config).await?;
+
+    let Some(target) = target else {
+        let tombstones = orchestrator.database.mappings.list_tombstones().await?;
+        if tombstones.is_empty() {
+            println!("🪦 No deleted Motion tasks to restore");
+            return Ok(());
+        }
+
+        println!("🪦 Deleted Motion Tasks ({} total)", tombstones.len());
+        for tombstone in &tombstones {
+            println!(
+                "  {} ({})",
+                tombstone.motion_task.name,
+                tombstone.motion_task_id()
+            );
+            println!(
+                "    Deleted: {} - {}",
+                tombstone.deleted_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                tombstone.reason
+            );
+        }
+        println!();
+        println!("Run `restore <task id or identifier>` to recreate one");
+        return Ok(());
+    };
+
+    let restored = orchestrator.restore(&config, target).await?;
+    println!(
+        "♻️  Restored {} as Motion task {}",
+        restored.name,
+        restored.id.as_deref().unwrap_or("unknown")
+    );
+    Ok(())
+}
+
+async fn handle_resume(config_path: Option<&str>, identifier: &str) -> Result<()> {
+    use linear_motion::config::ConfigLoader;
+    use linear_motion::sync::orchestrator::SyncOrchestrator;
+
+    let config_path = match config_path {
+        Some(path) => path.to_string(),
+        None => ConfigLoader::get_default_config_path()?
+            .to_string_lossy()
+            .to_string(),
+    };
+    let config = ConfigLoader::load_from_file(&config_path).await?;
+    let orchestrator = SyncOrchestrator::new(&config).await?;
+
+    let mapping = orchestrator.resume(&config, identifier).await?;
+    println!(
+        "▶️  {} will get a new Motion task on the next sync ({})",
+        mapping.identifier().as_deref().unwrap_or(identifier),
+        mapping.sync_source
+    );
     Ok(())
 }
 
@@ -255,7 +642,7 @@ async fn handle_complete(config_path: Option<&str>, task_id: Option<&str>) -> Re
     Ok(())
 }
 
-async fn handle_tasks(config_path: Option<&str>, waybar: bool) -> Result<()> {
+async fn handle_tasks(config_path: Option<&str>, waybar: bool, at_risk: bool) -> Result<()> {
     use chrono::Utc;
     use linear_motion::clients::motion::MotionClient;
     use linear_motion::config::ConfigLoader;
@@ -301,7 +688,7 @@ async fn handle_tasks(config_path: Option<&str>, waybar: bool) -> Result<()> {
                     (Some(start), Some(end)) if start <= now && now < end
                 )
             })
-            .or_else(|| tasks.iter().find(|t| t.scheduled_start.map_or(false, |s| s > now)))
+            .or_else(|| tasks.iter().find(|t| t.scheduled_start.is_some_and(|s| s > now)))
             .or_else(|| tasks.first());
 
         let next = current.and_then(|c| {
@@ -341,6 +728,23 @@ async fn handle_tasks(config_path: Option<&str>, waybar: bool) -> Result<()> {
                 "class": class,
             })
         );
+    } else if at_risk {
+        let at_risk: Vec<_> = tasks
+            .iter()
+            .filter_map(|t| {
+                t.at_risk_reason(t.due_date).map(|reason| {
+                    serde_json::json!({
+                        "id": t.id,
+                        "name": t.name,
+                        "dueDate": t.due_date,
+                        "scheduledEnd": t.scheduled_end,
+                        "reason": reason,
+                    })
+                })
+            })
+            .collect();
+
+        println!("{}", serde_json::to_string_pretty(&at_risk)?);
     } else {
         println!("{}", serde_json::to_string_pretty(&tasks)?);
     }
@@ -348,6 +752,124 @@ async fn handle_tasks(config_path: Option<&str>, waybar: bool) -> Result<()> {
     Ok(())
 }
 
+async fn handle_history(config_path: Option<&str>, identifier: &str, json: bool) -> Result<()> {
+    use linear_motion::config::ConfigLoader;
+    use linear_motion::db::SyncDatabase;
+
+    let config_path = match config_path {
+        Some(path) => path.to_string(),
+        None => ConfigLoader::get_default_config_path()?
+            .to_string_lossy()
+            .to_string(),
+    };
+    let config = ConfigLoader::load_from_file(&config_path).await?;
+    let database = SyncDatabase::new(config.database_path()).await?;
+
+    let events = database.history.events_for_identifier(identifier).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&events)?);
+        return Ok(());
+    }
+
+    if events.is_empty() {
+        println!("📜 No sync history for {}", identifier);
+        return Ok(());
+    }
+
+    println!("📜 History of {} ({} events)", identifier, events.len());
+    for event in &events {
+        let task = event
+            .motion_task_id
+            .as_deref()
+            .map(|id| format!(" [Motion task {}]", id))
+            .unwrap_or_default();
+        println!(
+            "  {}  {} - {}{}",
+            event.at.format("%Y-%m-%d %H:%M:%S UTC"),
+            event.sync_source,
+            event.kind,
+            task
+        );
+    }
+
+    Ok(())
+}
+
+async fn handle_db(config_path: Option<&str>, command: DbCommands) -> Result<()> {
+    use linear_motion::config::ConfigLoader;
+    use linear_motion::db::export::ImportConflict;
+    use linear_motion::db::SyncDatabase;
+    use std::path::PathBuf;
+
+    let config_path = match config_path {
+        Some(path) => path.to_string(),
+        None => ConfigLoader::get_default_config_path()?
+            .to_string_lossy()
+            .to_string(),
+    };
+    let config = ConfigLoader::load_from_file(&config_path).await?;
+    // The daemon doesn't hold the database open yet, so backups read it directly;
+    // every export comes from a single read snapshot either way
+    let database = SyncDatabase::new(config.database_path()).await?;
+
+    match command {
+        DbCommands::Export { output } => match output {
+            Some(output) => {
+                let file = std::fs::File::create(&output)?;
+                let exported = database.export(std::io::BufWriter::new(file))?;
+                println!("📤 Exported {} records to {}", exported, output);
+            }
+            None => {
+                database.export(std::io::stdout().lock())?;
+            }
+        },
+        DbCommands::Import {
+            input,
+            overwrite,
+            newer,
+        } => {
+            let conflict = if overwrite {
+                ImportConflict::Overwrite
+            } else if newer {
+                ImportConflict::Newer
+            } else {
+                ImportConflict::Skip
+            };
+            let file = std::fs::File::open(&input)?;
+            let summary = database.import(std::io::BufReader::new(file), conflict)?;
+            database.flush().await?;
+
+            println!(
+                "📥 Imported {} records from {} ({} already present and kept)",
+                summary.imported, input, summary.skipped
+            );
+            if !summary.rejected.is_empty() {
+                println!(
+                    "⚠️  {} lines could not be imported:",
+                    summary.rejected.len()
+                );
+                for (line, error) in &summary.rejected {
+                    println!("   line {}: {}", line, error);
+                }
+            }
+        }
+        DbCommands::Backup { dir } => {
+            let dir = match dir {
+                Some(dir) => PathBuf::from(dir),
+                None => config
+                    .database_path()
+                    .parent()
+                    .map(|parent| parent.join("backups"))
+                    .unwrap_or_else(|| PathBuf::from("backups")),
+            };
+            let path = database.backup(&dir)?;
+            println!("💾 Database backed up to {}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
 async fn handle_list(
     config_path: Option<&str>,
     verbose: bool,
@@ -407,6 +929,8 @@ async fn handle_list(
                 linear_motion::db::MappingStatus::Synced => "✅",
                 linear_motion::db::MappingStatus::Failed => "❌",
                 linear_motion::db::MappingStatus::Stale => "🔄",
+                linear_motion::db::MappingStatus::Completed => "☑️",
+                linear_motion::db::MappingStatus::Ignored => "🚫",
             };
 
             let status_str = match mapping.status {
@@ -414,6 +938,8 @@ async fn handle_list(
                 linear_motion::db::MappingStatus::Synced => "Synced",
                 linear_motion::db::MappingStatus::Failed => "Failed",
                 linear_motion::db::MappingStatus::Stale => "Stale",
+                linear_motion::db::MappingStatus::Completed => "Completed",
+                linear_motion::db::MappingStatus::Ignored => "Ignored",
             };
 
             println!("  {} {}", status_icon, issue_title);
@@ -422,6 +948,32 @@ async fn handle_list(
             if let Some(motion_id) = &mapping.motion_task_id {
                 println!("    Motion Task: {}", motion_id);
             }
+            if let Some(at_risk) = &mapping.at_risk {
+                println!("    ⚠️  At Risk: {}", at_risk.reason);
+            }
+            if let Some(since) = mapping.deletion_held_since {
+                println!(
+                    "    🛑 Deletion held since {}, run `sync --confirm-deletions` to delete",
+                    since.format("%Y-%m-%d %H:%M:%S UTC")
+                );
+            }
+            if let Some(deletion) = &mapping.downstream_deletion {
+                println!(
+                    "    🗑️  Deleted in Motion: {} ({:?}, {})",
+                    deletion.motion_task_id,
+                    deletion.outcome,
+                    deletion.detected_at.format("%Y-%m-%d %H:%M:%S UTC")
+                );
+                if matches!(mapping.status, linear_motion::db::MappingStatus::Ignored) {
+                    println!("    Run `resume <identifier>` to sync it again");
+                }
+            }
+            for conflict in &mapping.conflicts {
+                println!(
+                    "    🔀 Conflict: {} kept from Motion (Linear wants {})",
+                    conflict.field, conflict.linear_value
+                );
+            }
             println!(
                 "    Created: {}",
                 mapping.created_at.format("%Y-%m-%d %H:%M:%S UTC")
@@ -467,6 +1019,8 @@ async fn handle_list(
         }
     }
 
+    print_at_risk_report(&mappings);
+
     // Get and display sync status entries
     let status_entries = match source_filter {
         Some(source) => database.status.list_statuses_by_source(source).await?,
@@ -0,0 +1,164 @@
+use crate::clients::{linear::LinearIssue, motion::MotionTask};
+use std::collections::HashSet;
+
+/// Titles at least this similar are proposed as a match
+const TITLE_SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// Why a hand-made Motion task was matched to a Linear issue
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchReason {
+    /// The task name mentions the issue identifier
+    Identifier,
+    /// The task name is similar to the issue title, from 0 to 1
+    Title { similarity: f64 },
+}
+
+/// A proposed link between an existing Motion task and an assigned Linear issue
+#[derive(Debug, Clone)]
+pub struct AdoptionCandidate {
+    pub sync_source: String,
+    pub issue: LinearIssue,
+    pub task: MotionTask,
+    pub reason: MatchReason,
+}
+
+/// Propose links between unlabelled Motion tasks and unmapped Linear issues.
+///
+/// Identifier matches win over title matches, and each task and issue is used at most once.
+pub fn propose(tasks: &[MotionTask], issues: &[(String, LinearIssue)]) -> Vec<AdoptionCandidate> {
+    let mut scored = Vec::new();
+    for task in tasks.iter().filter(|task| !task.is_synced_task()) {
+        for (source, issue) in issues {
+            let reason = if mentions_identifier(&task.name, &issue.identifier) {
+                MatchReason::Identifier
+            } else {
+                let similarity = title_similarity(&task.name, &issue.title);
+                if similarity < TITLE_SIMILARITY_THRESHOLD {
+                    continue;
+                }
+                MatchReason::Title { similarity }
+            };
+            scored.push((source, issue, task, reason));
+        }
+    }
+
+    let score = |reason: &MatchReason| match reason {
+        MatchReason::Identifier => 2.0,
+        MatchReason::Title { similarity } => *similarity,
+    };
+    scored.sort_by(|a, b| score(&b.3).total_cmp(&score(&a.3)));
+
+    let mut used_tasks = HashSet::new();
+    let mut used_issues = HashSet::new();
+    let mut candidates = Vec::new();
+    for (source, issue, task, reason) in scored {
+        if !used_tasks.insert(task.id.clone()) {
+            continue;
+        }
+        if !used_issues.insert(issue.id.clone()) {
+            used_tasks.remove(&task.id);
+            continue;
+        }
+        candidates.push(AdoptionCandidate {
+            sync_source: source.clone(),
+            issue: issue.clone(),
+            task: task.clone(),
+            reason,
+        });
+    }
+
+    candidates
+}
+
+/// Whether `name` contains `identifier` as a whole word, ignoring case
+fn mentions_identifier(name: &str, identifier: &str) -> bool {
+    let name = name.to_lowercase();
+    let identifier = identifier.to_lowercase();
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '-';
+
+    name.match_indices(&identifier).any(|(start, _)| {
+        let before = name[..start].chars().next_back();
+        let after = name[start + identifier.len()..].chars().next();
+        !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+    })
+}
+
+/// Dice coefficient over the character bigrams of the normalised titles
+fn title_similarity(a: &str, b: &str) -> f64 {
+    let bigrams = |text: &str| -> Vec<(char, char)> {
+        let normalised: Vec<char> = text
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .collect();
+        normalised
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect()
+    };
+
+    let a = bigrams(a);
+    let mut b = bigrams(b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let total = a.len() + b.len();
+    let mut shared = 0;
+    for bigram in &a {
+        if let Some(index) = b.iter().position(|other| other == bigram) {
+            b.swap_remove(index);
+            shared += 1;
+        }
+    }
+
+    (2 * shared) as f64 / total as f64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::clients::testing::{issue, task};
+
+    #[test]
+    fn test_propose_adoptions() {
+        let issues = vec![
+            (
+                "personal".to_string(),
+                issue("issue-1", "CAR-1", "Reach out to dentist"),
+            ),
+            (
+                "personal".to_string(),
+                issue("issue-2", "CAR-12", "Book flights"),
+            ),
+        ];
+        let tasks = vec![
+            task("tk_1", "Reach out to the dentist"),
+            task("tk_2", "car-12: flights"),
+            task("tk_3", "Water the plants"),
+        ];
+
+        let links: Vec<_> = propose(&tasks, &issues)
+            .into_iter()
+            .map(|c| (c.task.id.unwrap(), c.issue.identifier, c.reason))
+            .collect();
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(
+            links[0],
+            (
+                "tk_2".to_string(),
+                "CAR-12".to_string(),
+                MatchReason::Identifier
+            )
+        );
+        assert_eq!(links[1].0, "tk_1");
+        assert!(matches!(links[1].2, MatchReason::Title { similarity } if similarity >= 0.8));
+
+        assert!(!mentions_identifier("CAR-123 something", "CAR-12"));
+        assert!(mentions_identifier("[CAR-12] something", "CAR-12"));
+    }
+}
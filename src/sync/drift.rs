@@ -0,0 +1,181 @@
+use crate::clients::motion::{MotionTask, TaskDuration, TaskUpdate};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt;
+
+/// A way a Motion task no longer matches what the sync last wrote
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum Drift {
+    /// The task no longer exists in Motion
+    Missing,
+    Name {
+        expected: String,
+        actual: String,
+    },
+    Duration {
+        expected: Option<u32>,
+        actual: Option<u32>,
+    },
+    Deadline {
+        expected: Option<DateTime<Utc>>,
+        actual: Option<DateTime<Utc>>,
+    },
+    MissingLabel {
+        label: String,
+    },
+}
+
+impl Drift {
+    /// The Motion API field that repairs this drift
+    pub fn field(&self) -> Option<&'static str> {
+        match self {
+            Drift::Missing => None,
+            Drift::Name { .. } => Some("name"),
+            Drift::Duration { .. } => Some("duration"),
+            Drift::Deadline { .. } => Some("dueDate"),
+            Drift::MissingLabel { .. } => Some("labels"),
+        }
+    }
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let or_none = |value: Option<String>| value.unwrap_or_else(|| "none".to_string());
+
+        match self {
+            Drift::Missing => write!(f, "task is missing from Motion"),
+            Drift::Name { expected, actual } => {
+                write!(f, "name is '{}', expected '{}'", actual, expected)
+            }
+            Drift::Duration { expected, actual } => write!(
+                f,
+                "duration is {}, expected {}",
+                or_none(actual.map(|m| format!("{} min", m))),
+                or_none(expected.map(|m| format!("{} min", m)))
+            ),
+            Drift::Deadline { expected, actual } => write!(
+                f,
+                "deadline is {}, expected {}",
+                or_none(actual.map(|d| d.to_rfc3339())),
+                or_none(expected.map(|d| d.to_rfc3339()))
+            ),
+            Drift::MissingLabel { label } => write!(f, "label '{}' is missing", label),
+        }
+    }
+}
+
+/// Drift found on a single mapped task
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftReport {
+    pub sync_source: String,
+    pub linear_issue_id: String,
+    pub identifier: String,
+    pub motion_task_id: String,
+    pub drift: Vec<Drift>,
+    /// Whether the drift was corrected in Motion
+    pub repaired: bool,
+}
+
+/// Compare a Motion task against the projection expected from the stored Linear issue,
+/// ignoring `skip` fields (Motion API names) that were deliberately edited in Motion
+pub fn detect(expected: &TaskUpdate, task: &MotionTask, skip: &[String]) -> Vec<Drift> {
+    let mut drift = Vec::new();
+    let skipped = |field: &str| skip.iter().any(|s| s == field);
+
+    if let Some(name) = &expected.name {
+        if !skipped("name") && *name != task.name {
+            drift.push(Drift::Name {
+                expected: name.clone(),
+                actual: task.name.clone(),
+            });
+        }
+    }
+
+    let minutes = |duration: &Option<TaskDuration>| match duration {
+        Some(TaskDuration::Minutes(minutes)) => Some(*minutes),
+        _ => None,
+    };
+    if expected.duration.is_some()
+        && !skipped("duration")
+        && minutes(&expected.duration) != minutes(&task.duration)
+    {
+        drift.push(Drift::Duration {
+            expected: minutes(&expected.duration),
+            actual: minutes(&task.duration),
+        });
+    }
+
+    if !skipped("dueDate") && expected.due_date != task.due_date {
+        drift.push(Drift::Deadline {
+            expected: expected.due_date,
+            actual: task.due_date,
+        });
+    }
+
+    for label in expected.labels.iter().flatten() {
+        let has_label = task
+            .labels
+            .iter()
+            .flatten()
+            .any(|task_label| task_label.name == *label);
+        if !has_label {
+            drift.push(Drift::MissingLabel {
+                label: label.clone(),
+            });
+        }
+    }
+
+    drift
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::clients::motion::Label;
+
+    #[test]
+    fn test_detect_drift() {
+        let deadline = "2025-03-04T23:59:00Z".parse::<DateTime<Utc>>().unwrap();
+        let expected = TaskUpdate {
+            name: Some("[CAR-1] Ship it".to_string()),
+            duration: Some(TaskDuration::Minutes(60)),
+            due_date: Some(deadline),
+            labels: Some(vec!["linear-sync".to_string()]),
+            ..Default::default()
+        };
+        let mut task = MotionTask {
+            name: "[CAR-1] Ship it".to_string(),
+            duration: Some(TaskDuration::Minutes(60)),
+            due_date: Some(deadline),
+            labels: Some(vec![Label {
+                name: "linear-sync".to_string(),
+            }]),
+            ..Default::default()
+        };
+
+        assert_eq!(detect(&expected, &task, &[]), vec![]);
+
+        task.name = "Renamed".to_string();
+        task.duration = Some(TaskDuration::Minutes(30));
+        task.labels = None;
+        let drift = detect(&expected, &task, &["duration".to_string()]);
+
+        assert_eq!(
+            drift,
+            vec![
+                Drift::Name {
+                    expected: "[CAR-1] Ship it".to_string(),
+                    actual: "Renamed".to_string(),
+                },
+                Drift::MissingLabel {
+                    label: "linear-sync".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            drift.iter().filter_map(Drift::field).collect::<Vec<_>>(),
+            vec!["name", "labels"]
+        );
+    }
+}
@@ -1,2 +1,5 @@
+pub mod adopt;
+pub mod drift;
 pub mod engine;
 pub mod orchestrator;
+pub mod reconcile;
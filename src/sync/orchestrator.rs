@@ -1,11 +1,28 @@
 use crate::clients::{
-    linear::LinearClient,
-    motion::{AutoScheduled, Label, MotionClient, MotionTask, MotionWorkspace, Status},
+    linear::{IssueUpdate, LinearClient, LinearIssue},
+    motion::{
+        AutoScheduled, Label, MotionClient, MotionTask, MotionWorkspace, Status, TaskUpdate,
+        COMPLETED_STATUS,
+    },
 };
-use crate::config::{AppConfig, SyncRules, SyncSource};
-use crate::db::SyncDatabase;
+use crate::config::{
+    AppConfig, ConflictPolicy, DeadlineInputs, DeletedTaskPolicy, DriftMode, ScheduleFeedbackMode,
+    SyncRules, SyncSource, TerminalAction,
+};
+use crate::db::{
+    history::{SyncEvent, SyncEventKind},
+    mapping::{
+        DeletionOutcome, FeedbackTarget, FieldConflict, MotionSnapshot, ScheduleFeedbackRecord,
+        TaskMapping, Tombstone,
+    },
+    MappingStatus, SyncDatabase, SyncStatus,
+};
+use crate::sync::adopt::{self, AdoptionCandidate};
+use crate::sync::drift::{self, Drift, DriftReport};
+use crate::sync::reconcile::{self, ReconcileAction, ReconcileEntry};
 use crate::{Error, Result};
-use chrono::Days;
+use chrono_tz::Tz;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
@@ -27,7 +44,12 @@ impl SyncOrchestrator {
         })
     }
 
-    pub async fn run_full_sync(&self, config: &AppConfig, force_update: bool) -> Result<()> {
+    pub async fn run_full_sync(
+        &self,
+        config: &AppConfig,
+        force_update: bool,
+        confirm_deletions: bool,
+    ) -> Result<()> {
         debug!(
             "starting full sync for {} sources",
             config.sync_sources.len()
@@ -42,6 +64,8 @@ impl SyncOrchestrator {
             }
         }
 
+        let workspace = Self::target_workspace(&self.motion_client).await?;
+
         // Process each sync source in parallel
         use futures::future::join_all;
 
@@ -51,15 +75,19 @@ impl SyncOrchestrator {
             .map(|source| {
                 let database = Arc::clone(&self.database);
                 let motion_client = Arc::clone(&self.motion_client);
+                let workspace = &workspace;
                 let source = source.clone();
                 let global_rules = config.global_sync_rules.clone();
+                let timezone = config.timezone();
 
                 async move {
                     let result = Self::sync_source(
                         database.clone(),
                         motion_client,
+                        workspace,
                         &source,
                         &global_rules,
+                        timezone,
                         force_update,
                     )
                     .await;
@@ -88,7 +116,10 @@ impl SyncOrchestrator {
         self.database.flush().await?;
 
         // Clean up Motion tasks for Linear issues that are no longer assigned
-        if let Err(e) = self.cleanup_unassigned_tasks(config).await {
+        if let Err(e) = self
+            .cleanup_unassigned_tasks(config, confirm_deletions)
+            .await
+        {
             error!("Failed to cleanup unassigned tasks: {}", e);
             // Don't fail the whole sync if cleanup fails
         }
@@ -99,6 +130,37 @@ impl SyncOrchestrator {
             // Don't fail the whole sync if completion sync fails
         }
 
+        // Report Motion's schedule back to Linear
+        match self.motion_tasks_by_id(&workspace).await {
+            Ok(tasks) => {
+                if let Err(e) = self.sync_schedule_feedback(config, &tasks).await {
+                    error!("Failed to sync schedule feedback: {}", e);
+                    // Don't fail the whole sync if schedule feedback fails
+                }
+
+                if let Err(e) = self.check_at_risk(config, &tasks).await {
+                    error!("Failed to check for at-risk tasks: {}", e);
+                }
+
+                // Compare Motion with the stored state when a drift check is due
+                if let Err(e) = self.check_drift(config, Some(&tasks), None, None).await {
+                    error!("Failed to check for drift: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to fetch Motion tasks: {}", e),
+        }
+
+        match self
+            .database
+            .mappings
+            .purge_tombstones(config.tombstone_retention_days)
+            .await
+        {
+            Ok(0) => {}
+            Ok(purged) => info!("Purged {} expired tombstones", purged),
+            Err(e) => error!("Failed to purge expired tombstones: {}", e),
+        }
+
         // Flush database changes again after completion sync
         self.database.flush().await?;
 
@@ -107,12 +169,14 @@ impl SyncOrchestrator {
     }
 
     // write source name to span
-    #[tracing::instrument(skip(database, motion_client, source, global_rules), fields(source = source.name.as_str()))]
+    #[tracing::instrument(skip(database, motion_client, workspace, source, global_rules, timezone), fields(source = source.name.as_str()))]
     async fn sync_source(
         database: Arc<SyncDatabase>,
         motion_client: Arc<MotionClient>,
+        workspace: &MotionWorkspace,
         source: &SyncSource,
         global_rules: &SyncRules,
+        timezone: Tz,
         force_update: bool,
     ) -> Result<usize> {
         debug!("syncing source: {}", source.name);
@@ -158,15 +222,81 @@ impl SyncOrchestrator {
             }
         }
 
+        // Make sure the sync rules only refer to statuses Motion knows about
+        Self::validate_statuses(workspace, &sync_rules)?;
+
         // Fetch assigned issues from Linear
         let issues = linear_client.get_assigned_issues(projects).await?;
         info!("Found {} assigned issues in Linear", issues.len());
         debug!("found issues: {:?}", issues);
 
+        // Current Motion tasks, to tell edits made in Motion apart from our own writes
+        let motion_tasks: HashMap<String, MotionTask> = motion_client
+            .list_tasks(&workspace.id)
+            .await?
+            .into_iter()
+            .filter_map(|task| task.id.clone().map(|id| (id, task)))
+            .collect();
+
         let mut synced_count = 0;
 
         // Process each issue
         for issue in &issues {
+            // Check if we already have a mapping for this issue
+            let mut existing_mapping = database
+                .mappings
+                .get_mapping_by_linear_id(&source.name, &issue.id)
+                .await?;
+
+            // A completed mapping either means the work is done, or the issue has since been
+            // reopened in Linear and should be treated as new
+            let reopened = match &existing_mapping {
+                Some(mapping) if matches!(mapping.status, MappingStatus::Completed) => {
+                    match Self::was_reopened(mapping, issue) {
+                        Ok(reopened) => Some(reopened),
+                        Err(e) => {
+                            warn!(
+                                "Failed to check whether {} was reopened, skipping: {}",
+                                issue.identifier, e
+                            );
+                            continue;
+                        }
+                    }
+                }
+                _ => None,
+            };
+
+            match reopened {
+                Some(false) => {
+                    debug!("Issue {} already completed, skipping", issue.identifier);
+                    continue;
+                }
+                Some(true) => {
+                    info!(
+                        "Issue {} was reopened, creating a fresh Motion task",
+                        issue.identifier
+                    );
+                    // Keep the mapping until the label is gone, or the issue would be skipped as
+                    // already completed from then on
+                    if let Err(e) = linear_client
+                        .remove_label_from_issue(&issue.id, &sync_rules.completed_linear_tag)
+                        .await
+                    {
+                        warn!(
+                            "Failed to remove '{}' from reopened issue {}, retrying next sync: {}",
+                            sync_rules.completed_linear_tag, issue.identifier, e
+                        );
+                        continue;
+                    }
+                    database
+                        .mappings
+                        .remove_mapping(&source.name, &issue.id)
+                        .await?;
+                    existing_mapping = None;
+                }
+                None => {}
+            }
+
             // Check if issue already has the completion tag
             if linear_client
                 .check_issue_has_label(&issue.id, &sync_rules.completed_linear_tag)
@@ -179,20 +309,56 @@ impl SyncOrchestrator {
                 continue;
             }
 
-            // Check if we already have a mapping for this issue
-            let existing_mapping = database
-                .mappings
-                .get_mapping_by_linear_id(&source.name, &issue.id)
-                .await?;
+            // Push edits made in Motion back to Linear before comparing against Linear
+            let pushed_issue: LinearIssue;
+            let mut issue = issue;
+            let motion_task = existing_mapping
+                .as_ref()
+                .filter(|_| sync_rules.reverse_sync.any())
+                .filter(|mapping| matches!(mapping.status, MappingStatus::Synced))
+                .and_then(|mapping| mapping.motion_task_id.as_ref())
+                .and_then(|id| motion_tasks.get(id));
+
+            if let (Some(mapping), Some(task)) = (&existing_mapping, motion_task) {
+                match Self::push_motion_edits(
+                    &database,
+                    &linear_client,
+                    mapping,
+                    issue,
+                    task,
+                    &sync_rules,
+                    timezone,
+                )
+                .await
+                {
+                    Ok(Some(updated_issue)) => {
+                        pushed_issue = updated_issue;
+                        issue = &pushed_issue;
+                        existing_mapping = database
+                            .mappings
+                            .get_mapping_by_linear_id(&source.name, &issue.id)
+                            .await?;
+                    }
+                    Ok(None) => {}
+                    Err(e) => warn!(
+                        "Failed to push Motion edits for {} to Linear: {}",
+                        issue.identifier, e
+                    ),
+                }
+            }
 
-            let _mapping = match existing_mapping {
+            let mapping = match existing_mapping {
                 Some(mapping) => {
                     debug!(
                         "Issue {} already tracked with status: {:?}",
                         issue.identifier, mapping.status
                     );
+                    if matches!(mapping.status, MappingStatus::Ignored) {
+                        debug!("Issue {} is ignored, skipping", issue.identifier);
+                        continue;
+                    }
                     // If it's already synced successfully, check if it needs updating
-                    if matches!(mapping.status, crate::db::MappingStatus::Synced) {
+                    if matches!(mapping.status, MappingStatus::Synced) {
                         // Check if the issue has been updated since last sync or if force update is enabled
                         if force_update || Self::issue_needs_update(&mapping, issue)? {
                             if force_update {
@@ -216,6 +382,14 @@ impl SyncOrchestrator {
                     mapping
                 }
                 None => {
+                    if let Some(starts_at) = Self::held_until(issue, &sync_rules) {
+                        debug!(
+                            "Issue {} is in a future cycle, holding until {}",
+                            issue.identifier, starts_at
+                        );
+                        continue;
+                    }
+
                     debug!(
                         "Processing new issue: {} - {}",
                         issue.identifier, issue.title
@@ -236,31 +410,74 @@ impl SyncOrchestrator {
                 .await?;
 
             // Determine if this is an update or create operation
-            let is_update = _mapping.motion_task_id.is_some();
+            let is_update = mapping.motion_task_id.is_some();
 
             if is_update {
-                // Update existing Motion task
-                let motion_task_id = _mapping.motion_task_id.as_ref().unwrap();
-                match Self::update_motion_task_from_issue(
-                    &motion_client,
-                    motion_task_id,
+                // Update existing Motion task with only the fields that changed in Linear
+                let motion_task_id = mapping.motion_task_id.as_ref().unwrap();
+                let projection = Self::task_projection(
                     issue,
                     &sync_rules,
+                    timezone,
+                    mapping.created_at,
                     &source.name,
-                )
-                .await
-                {
-                    Ok(_) => {
-                        // Update the stored Linear issue data in the mapping
-                        database
-                            .mappings
-                            .update_issue_data(&source.name, &issue.id, issue)
-                            .await?;
+                );
+                let (update, conflicts) = Self::merge_update(
+                    &projection,
+                    &mapping,
+                    motion_tasks.get(motion_task_id),
+                    sync_rules.conflict_policy,
+                    force_update,
+                )?;
+
+                for conflict in &conflicts {
+                    warn!(
+                        "⚠️  {} {} was edited in both Linear and Motion, keeping Motion's value",
+                        issue.identifier, conflict.field
+                    );
+                }
 
-                        // Update status as completed
+                let result = if update.is_empty() {
+                    Ok(None)
+                } else {
+                    motion_client
+                        .update_task_fields(motion_task_id, &update)
+                        .await
+                        .map(Some)
+                };
+
+                match result {
+                    Ok(updated_task) => {
+                        debug!(
+                            "Updated fields {:?} on Motion task {}",
+                            update.fields(),
+                            motion_task_id
+                        );
+
+                        // Store the issue data, the task and the projection with the status
                         database
-                            .status
-                            .mark_completed(&status_entry.id, motion_task_id.clone())
+                            .record_outcome(
+                                &source.name,
+                                &issue.id,
+                                &status_entry.id,
+                                |stored| {
+                                    stored.set_issue_data(issue)?;
+                                    stored.sync_error = None;
+                                    if let Some(updated_task) = &updated_task {
+                                        stored.set_motion_snapshot(updated_task);
+                                    }
+                                    stored.record_projection(projection, &update, conflicts);
+                                    Ok((!update.is_empty()).then(|| {
+                                        SyncEvent::new(
+                                            &mapping,
+                                            SyncEventKind::Updated {
+                                                fields: update.fields(),
+                                            },
+                                        )
+                                    }))
+                                },
+                                |entry| entry.set_completed(motion_task_id.clone()),
+                            )
                             .await?;
 
                         synced_count += 1;
@@ -269,41 +486,142 @@ impl SyncOrchestrator {
                             issue.identifier, motion_task_id
                         );
                     }
+                    Err(Error::NotFound(_)) => {
+                        let policy = sync_rules.deleted_task_policy;
+                        warn!(
+                            "🗑️  Motion task {} for {} was deleted in Motion, applying {:?}",
+                            motion_task_id, issue.identifier, policy
+                        );
+
+                        // A failed label must not keep the deletion from being recorded
+                        let mut label_error = None;
+                        if policy == DeletedTaskPolicy::Label {
+                            if let Err(e) = linear_client
+                                .add_label_to_issue(&issue.id, &sync_rules.deleted_task_label)
+                                .await
+                            {
+                                warn!(
+                                    "Failed to label {} with '{}': {}",
+                                    issue.identifier, sync_rules.deleted_task_label, e
+                                );
+                                label_error = Some(format!(
+                                    "Failed to add the '{}' label: {}",
+                                    sync_rules.deleted_task_label, e
+                                ));
+                            }
+                        }
+
+                        database
+                            .record_outcome(
+                                &source.name,
+                                &issue.id,
+                                &status_entry.id,
+                                |stored| {
+                                    stored.mark_deleted_downstream(Self::deletion_outcome(policy));
+                                    stored.sync_error = label_error;
+                                    Ok(Some(SyncEvent::new(
+                                        &mapping,
+                                        SyncEventKind::Deleted {
+                                            reason: "Motion task was deleted in Motion".to_string(),
+                                        },
+                                    )))
+                                },
+                                |entry| {
+                                    entry.set_status(
+                                        SyncStatus::Failed,
+                                        Some("Motion task was deleted".to_string()),
+                                    )
+                                },
+                            )
+                            .await?;
+                    }
                     Err(e) => {
                         error!(
                             "❌ Failed to update Motion task for {}: {}",
                             issue.identifier, e
                         );
 
+                        // The task still exists, so the mapping stays synced to it
                         database
-                            .status
-                            .mark_failed(&status_entry.id, e.to_string())
+                            .record_outcome(
+                                &source.name,
+                                &issue.id,
+                                &status_entry.id,
+                                |stored| {
+                                    stored.set_update_failed(e.to_string());
+                                    Ok(Some(SyncEvent::new(
+                                        &mapping,
+                                        SyncEventKind::Error {
+                                            message: e.to_string(),
+                                        },
+                                    )))
+                                },
+                                |entry| entry.set_status(SyncStatus::Failed, Some(e.to_string())),
+                            )
                             .await?;
                     }
                 }
             } else {
+                // A previous run may have created the task without recording its id
+                let recovered =
+                    Self::recover_created_task(&database, &mapping, &motion_tasks).await?;
+                let created = match recovered {
+                    Some(task) => {
+                        info!(
+                            "♻️  Adopting Motion task {} left by an interrupted create for {}",
+                            task.id.as_deref().unwrap_or("unknown"),
+                            issue.identifier
+                        );
+                        Ok(task)
+                    }
+                    None => {
+                        database
+                            .mappings
+                            .record_create_intent(&source.name, &issue.id, &issue.identifier)
+                            .await?;
+                        Self::create_motion_task_from_issue(
+                            &motion_client,
+                            issue,
+                            &sync_rules,
+                            timezone,
+                            mapping.created_at,
+                            &source.name,
+                        )
+                        .await
+                    }
+                };
+
                 // Create new Motion task
-                match Self::create_motion_task_from_issue(
-                    &motion_client,
-                    issue,
-                    &sync_rules,
-                    &source.name,
-                )
-                .await
-                {
+                match created {
                     Ok(motion_task) => {
                         let motion_task_id = motion_task.id.clone().unwrap_or_default();
 
-                        // Mark mapping as synced with Motion task ID
-                        database
-                            .mappings
-                            .mark_synced(&source.name, &issue.id, motion_task_id.clone())
-                            .await?;
+                        let projection = Self::task_projection(
+                            issue,
+                            &sync_rules,
+                            timezone,
+                            mapping.created_at,
+                            &source.name,
+                        );
 
-                        // Update status as completed
+                        // Mark mapping as synced and its status as completed together
                         database
-                            .status
-                            .mark_completed(&status_entry.id, motion_task_id)
+                            .record_outcome(
+                                &source.name,
+                                &issue.id,
+                                &status_entry.id,
+                                |stored| {
+                                    stored.set_synced(motion_task_id.clone());
+                                    stored.set_motion_snapshot(&motion_task);
+                                    stored.record_projection(
+                                        projection.clone(),
+                                        &projection,
+                                        Vec::new(),
+                                    );
+                                    Ok(Some(SyncEvent::new(stored, SyncEventKind::Created)))
+                                },
+                                |entry| entry.set_completed(motion_task_id.clone()),
+                            )
                             .await?;
 
                         synced_count += 1;
@@ -315,15 +633,9 @@ impl SyncOrchestrator {
                             issue.identifier, e
                         );
 
-                        // Mark mapping as failed
-                        database
-                            .mappings
-                            .mark_failed(&source.name, &issue.id, e.to_string())
-                            .await?;
-
+                        // Mark mapping and status as failed together
                         database
-                            .status
-                            .mark_failed(&status_entry.id, e.to_string())
+                            .mark_failed(&source.name, &issue.id, &status_entry.id, e.to_string())
                             .await?;
                     }
                 }
@@ -339,10 +651,10 @@ impl SyncOrchestrator {
         Ok(synced_count)
     }
 
-    /// Check for completed tasks in Motion and tag corresponding Linear issues
+    /// Check for completed tasks in Motion and apply each source's completion action in Linear
     #[tracing::instrument(skip(self, config))]
     pub async fn sync_completed_tasks(&self, config: &AppConfig) -> Result<()> {
-        info!("Checking for completed Motion tasks to tag in Linear");
+        info!("Checking for completed Motion tasks to report to Linear");
 
         // Get Motion workspaces
         let workspaces = self.motion_client.list_workspaces().await?;
@@ -387,7 +699,11 @@ impl SyncOrchestrator {
                     .get_mapping_by_motion_id(task_id)
                     .await?
                 {
-                    // Check if this issue is already tagged to avoid re-tagging
+                    // Completed mappings were already handled on an earlier pass
+                    if matches!(mapping.status, MappingStatus::Completed) {
+                        continue;
+                    }
+
                     if let Some(sync_source_config) = config
                         .sync_sources
                         .iter()
@@ -397,52 +713,69 @@ impl SyncOrchestrator {
                             sync_source_config.linear_api_key.clone(),
                         )?;
 
-                        let completion_tag = &config.global_sync_rules.completed_linear_tag;
-
-                        // Check if issue already has the completion tag
-                        if linear_client
-                            .check_issue_has_label(&mapping.linear_issue_id, completion_tag)
-                            .await?
-                        {
-                            debug!(
-                                "Linear issue {} already has completion tag, skipping",
-                                mapping.linear_issue_id
-                            );
-                            continue;
-                        }
+                        let sync_rules =
+                            sync_source_config.effective_sync_rules(&config.global_sync_rules);
 
                         info!(
-                            "Motion task {} completed, tagging Linear issue {}",
+                            "Motion task {} completed, updating Linear issue {}",
                             task_id, mapping.linear_issue_id
                         );
 
-                        // Add the completion tag to the Linear issue
-                        match linear_client
-                            .add_label_to_issue(&mapping.linear_issue_id, completion_tag)
-                            .await
+                        match Self::apply_completion_action(
+                            &linear_client,
+                            &mapping.linear_issue_id,
+                            &sync_rules,
+                        )
+                        .await
                         {
                             Ok(()) => {
                                 info!(
-                                    "✅ Successfully tagged Linear issue {} with '{}'",
-                                    mapping.linear_issue_id, completion_tag
+                                    "✅ Applied completion action to Linear issue {}",
+                                    mapping.linear_issue_id
                                 );
 
-                                // Remove the mapping from the database as per PRD
+                                // Keep the mapping so a reopened issue gets a fresh task
                                 self.database
                                     .mappings
-                                    .remove_mapping(&mapping.sync_source, &mapping.linear_issue_id)
+                                    .mark_completed(
+                                        &mapping.sync_source,
+                                        &mapping.linear_issue_id,
+                                        None,
+                                    )
+                                    .await?;
+
+                                self.database
+                                    .history
+                                    .record(SyncEvent::new(
+                                        &mapping,
+                                        SyncEventKind::Completed {
+                                            reason: "Motion task was completed".to_string(),
+                                        },
+                                    ))
                                     .await?;
 
                                 info!(
-                                    "🗑️  Removed mapping for completed task: {} -> {}",
+                                    "Marked mapping as completed: {} -> {}",
                                     mapping.linear_issue_id, task_id
                                 );
                             }
                             Err(e) => {
                                 error!(
-                                    "❌ Failed to tag Linear issue {}: {}",
+                                    "❌ Failed to update Linear issue {}: {}",
                                     mapping.linear_issue_id, e
                                 );
+                                self.database
+                                    .history
+                                    .record(SyncEvent::new(
+                                        &mapping,
+                                        SyncEventKind::Error {
+                                            message: format!(
+                                                "Failed to apply the completion action: {}",
+                                                e
+                                            ),
+                                        },
+                                    ))
+                                    .await?;
                             }
                         }
                     }
@@ -453,110 +786,122 @@ impl SyncOrchestrator {
         Ok(())
     }
 
-    /// Clean up Motion tasks for Linear issues that are no longer assigned to the user
-    #[tracing::instrument(skip(self, config))]
-    pub async fn cleanup_unassigned_tasks(&self, config: &AppConfig) -> Result<()> {
-        info!("Cleaning up Motion tasks for unassigned Linear issues");
+    /// Keep a comment or attachment on each synced Linear issue showing when Motion scheduled it
+    #[tracing::instrument(skip(self, config, tasks))]
+    pub async fn sync_schedule_feedback(
+        &self,
+        config: &AppConfig,
+        tasks: &HashMap<String, MotionTask>,
+    ) -> Result<()> {
+        let sources: Vec<(&SyncSource, SyncRules)> = config
+            .sync_sources
+            .iter()
+            .map(|source| {
+                (
+                    source,
+                    source.effective_sync_rules(&config.global_sync_rules),
+                )
+            })
+            .filter(|(_, rules)| rules.schedule_feedback.mode != ScheduleFeedbackMode::Off)
+            .collect();
 
-        for source in &config.sync_sources {
-            debug!("Checking for orphaned tasks from source: {}", source.name);
+        if sources.is_empty() {
+            return Ok(());
+        }
 
-            // Get all mappings for this source
-            let existing_mappings = self
+        info!("Posting Motion schedules to Linear");
+        let timezone = config.timezone();
+
+        for (source, sync_rules) in sources {
+            let linear_client = LinearClient::new(source.linear_api_key.clone())?;
+            let feedback = &sync_rules.schedule_feedback;
+
+            let mappings = self
                 .database
                 .mappings
                 .list_mappings_by_source(&source.name)
                 .await?;
 
-            if existing_mappings.is_empty() {
-                debug!("No existing mappings found for source: {}", source.name);
-                continue;
-            }
-
-            // Get currently assigned issues from Linear
-            let linear_client = LinearClient::new(source.linear_api_key.clone())?;
-            let current_issues = match linear_client.get_assigned_issues(source.projects.clone()).await {
-                Ok(issues) => issues,
-                Err(e) => {
-                    error!("Failed to fetch current issues for source '{}': {}", source.name, e);
-                    continue; // Skip this source if we can't fetch current issues
+            for mapping in mappings {
+                if !matches!(mapping.status, MappingStatus::Synced) {
+                    continue;
                 }
-            };
-
-            // Create a set of currently assigned issue IDs for quick lookup
-            let current_issue_ids: std::collections::HashSet<String> = 
-                current_issues.iter().map(|issue| issue.id.clone()).collect();
-
-            // Find orphaned mappings (exist in DB but issue is no longer assigned)
-            let orphaned_mappings: Vec<&crate::db::mapping::TaskMapping> = existing_mappings
-                .iter()
-                .filter(|mapping| !current_issue_ids.contains(&mapping.linear_issue_id))
-                .collect();
-
-            info!(
-                "Found {} orphaned mappings for source '{}' (out of {} total mappings)",
-                orphaned_mappings.len(),
-                source.name,
-                existing_mappings.len()
-            );
+                let Some(task) = mapping.motion_task_id.as_ref().and_then(|id| tasks.get(id))
+                else {
+                    continue;
+                };
 
-            // Delete Motion tasks and remove mappings for orphaned items
-            for mapping in orphaned_mappings {
-                if let Some(motion_task_id) = &mapping.motion_task_id {
-                    debug!(
-                        "Deleting Motion task {} for unassigned Linear issue {}",
-                        motion_task_id, mapping.linear_issue_id
-                    );
+                let target = match feedback.mode {
+                    ScheduleFeedbackMode::Comment => FeedbackTarget::Comment,
+                    _ => FeedbackTarget::Attachment,
+                };
+                let blocks = Self::scheduled_blocks(task, timezone);
+                let text = match target {
+                    FeedbackTarget::Comment => Self::schedule_comment(&blocks),
+                    FeedbackTarget::Attachment => Self::schedule_title(&blocks),
+                };
 
-                    // Delete the Motion task
-                    match self.motion_client.delete_task(motion_task_id).await {
-                        Ok(()) => {
-                            info!(
-                                "✅ Deleted Motion task {} for unassigned issue {}",
-                                motion_task_id, mapping.linear_issue_id
-                            );
+                // Only touch Linear when the schedule actually changed
+                let previous = mapping
+                    .schedule_feedback
+                    .as_ref()
+                    .filter(|record| record.mode == target);
+                if previous.is_some_and(|record| record.text == text) {
+                    continue;
+                }
 
-                            // Remove the mapping from the database
-                            if let Err(e) = self
-                                .database
-                                .mappings
-                                .remove_mapping(&source.name, &mapping.linear_issue_id)
+                let result = match target {
+                    FeedbackTarget::Comment => {
+                        let updated = match previous {
+                            Some(record) => linear_client
+                                .update_comment(&record.linear_id, &text)
                                 .await
-                            {
-                                error!(
-                                    "Failed to remove mapping for {}: {}",
-                                    mapping.linear_issue_id, e
-                                );
-                            } else {
-                                debug!(
-                                    "Removed mapping for unassigned issue: {}",
-                                    mapping.linear_issue_id
-                                );
+                                .map(|_| record.linear_id.clone())
+                                .ok(),
+                            None => None,
+                        };
+
+                        // Post a new comment if there was none or it has been deleted
+                        match updated {
+                            Some(comment_id) => Ok(comment_id),
+                            None => {
+                                linear_client
+                                    .create_comment(&mapping.linear_issue_id, &text)
+                                    .await
                             }
                         }
-                        Err(e) => {
-                            error!(
-                                "❌ Failed to delete Motion task {} for unassigned issue {}: {}",
-                                motion_task_id, mapping.linear_issue_id, e
-                            );
-                            // Don't remove the mapping if we couldn't delete the Motion task
-                        }
                     }
-                } else {
-                    // Mapping exists but no Motion task was created yet - just remove the mapping
-                    debug!(
-                        "Removing orphaned mapping without Motion task for issue: {}",
-                        mapping.linear_issue_id
-                    );
+                    FeedbackTarget::Attachment => {
+                        let url =
+                            feedback.attachment_url_for(task.id.as_deref().unwrap_or_default());
+                        linear_client
+                            .upsert_attachment(&mapping.linear_issue_id, &url, &text, None)
+                            .await
+                    }
+                };
 
-                    if let Err(e) = self
-                        .database
-                        .mappings
-                        .remove_mapping(&source.name, &mapping.linear_issue_id)
-                        .await
-                    {
-                        error!(
-                            "Failed to remove orphaned mapping for {}: {}",
+                match result {
+                    Ok(linear_id) => {
+                        debug!(
+                            "Posted schedule for Linear issue {}: {}",
+                            mapping.linear_issue_id, text
+                        );
+                        self.database
+                            .mappings
+                            .set_schedule_feedback(
+                                &source.name,
+                                &mapping.linear_issue_id,
+                                ScheduleFeedbackRecord {
+                                    mode: target,
+                                    linear_id,
+                                    text,
+                                },
+                            )
+                            .await?;
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Failed to post schedule for Linear issue {}: {}",
                             mapping.linear_issue_id, e
                         );
                     }
@@ -564,44 +909,1567 @@ impl SyncOrchestrator {
             }
         }
 
-        info!("Cleanup of unassigned tasks completed");
         Ok(())
     }
 
-    /// Format the description with a link back to the Linear issue
-    fn format_description_with_link(
-        issue: &crate::clients::linear::LinearIssue,
-        sync_source_name: &str,
-    ) -> Option<String> {
-        let base_description = issue.description.as_ref().cloned().unwrap_or_default();
-        let linear_link = format!(
-            "https://linear.app/{}/issue/{}",
-            sync_source_name, issue.identifier
-        );
+    /// The Motion task an interrupted create left for `mapping`, if no other mapping owns it.
+    ///
+    /// Creates happen in the target workspace, so the listing of its open tasks covers them.
+    async fn recover_created_task(
+        database: &SyncDatabase,
+        mapping: &TaskMapping,
+        motion_tasks: &HashMap<String, MotionTask>,
+    ) -> Result<Option<MotionTask>> {
+        let Some(intent) = &mapping.create_intent else {
+            return Ok(None);
+        };
 
-        if base_description.is_empty() {
-            Some(format!("Linear: {}", linear_link))
-        } else {
-            Some(format!("{}\n\nLinear: {}", base_description, linear_link))
+        for (id, task) in motion_tasks.iter().filter(|(_, task)| intent.matches(task)) {
+            if database
+                .mappings
+                .get_mapping_by_motion_id(id)
+                .await?
+                .is_none()
+            {
+                return Ok(Some(task.clone()));
+            }
         }
+        Ok(None)
     }
 
-    /// Check if a Linear issue has changes that require updating the Motion task
-    fn issue_needs_update(
-        mapping: &crate::db::mapping::TaskMapping,
-        current_issue: &crate::clients::linear::LinearIssue,
-    ) -> Result<bool> {
-        // Parse the stored issue data from the mapping
-        let stored_issue: crate::clients::linear::LinearIssue =
-            serde_json::from_value(mapping.linear_issue_data.clone())
-                .map_err(|e| Error::Json(e))?;
+    /// All tasks in the target workspace by ID, fetched once for the passes that read them
+    async fn motion_tasks_by_id(
+        &self,
+        workspace: &MotionWorkspace,
+    ) -> Result<HashMap<String, MotionTask>> {
+        Ok(self
+            .motion_client
+            .list_tasks(&workspace.id)
+            .await?
+            .into_iter()
+            .filter_map(|task| task.id.clone().map(|id| (id, task)))
+            .collect())
+    }
 
-        // Compare key fields that affect Motion tasks
-        let needs_update = stored_issue.title != current_issue.title
-            || stored_issue.description != current_issue.description
+    /// Flag tasks Motion can't schedule or schedules past their Linear due date
+    #[tracing::instrument(skip(self, config, tasks))]
+    pub async fn check_at_risk(
+        &self,
+        config: &AppConfig,
+        tasks: &HashMap<String, MotionTask>,
+    ) -> Result<()> {
+        let timezone = config.timezone();
+
+        for source in &config.sync_sources {
+            let sync_rules = source.effective_sync_rules(&config.global_sync_rules);
+            let linear_client = LinearClient::new(source.linear_api_key.clone())?;
+
+            let mappings = self
+                .database
+                .mappings
+                .list_mappings_by_source(&source.name)
+                .await?;
+
+            for mapping in mappings {
+                if !matches!(mapping.status, MappingStatus::Synced) {
+                    continue;
+                }
+                let Some(task) = mapping.motion_task_id.as_ref().and_then(|id| tasks.get(id))
+                else {
+                    continue;
+                };
+
+                let issue = Self::stored_issue(&mapping).ok();
+                let deadline = issue
+                    .as_ref()
+                    .and_then(|issue| issue.due_date.as_deref())
+                    .and_then(|due_date| sync_rules.deadline_for_due_date(due_date, timezone));
+                let reason = task.at_risk_reason(deadline);
+
+                let was_at_risk = mapping.at_risk.is_some();
+                let unchanged = mapping.at_risk.as_ref().map(|a| &a.reason) == reason.as_ref();
+                // At-risk issues are still checked for the label, in case it was removed in Linear
+                if unchanged && (reason.is_none() || sync_rules.at_risk_label.is_none()) {
+                    continue;
+                }
+
+                let identifier = issue
+                    .as_ref()
+                    .map_or(mapping.linear_issue_id.as_str(), |i| i.identifier.as_str());
+                if !unchanged {
+                    match &reason {
+                        Some(reason) => warn!("⚠️  {} is at risk: {}", identifier, reason),
+                        None => info!("{} is back on schedule", identifier),
+                    }
+                }
+
+                if let Some(label) = &sync_rules.at_risk_label {
+                    let result = match &reason {
+                        Some(_) => {
+                            match linear_client
+                                .check_issue_has_label(&mapping.linear_issue_id, label)
+                                .await
+                            {
+                                Ok(true) => Ok(()),
+                                Ok(false) => {
+                                    linear_client
+                                        .add_label_to_issue(&mapping.linear_issue_id, label)
+                                        .await
+                                }
+                                Err(e) => Err(e),
+                            }
+                        }
+                        None if was_at_risk => {
+                            linear_client
+                                .remove_label_from_issue(&mapping.linear_issue_id, label)
+                                .await
+                        }
+                        None => Ok(()),
+                    };
+
+                    if let Err(e) = result {
+                        warn!(
+                            "Failed to update '{}' label on {}: {}",
+                            label, identifier, e
+                        );
+                        // Try again on the next sync
+                        continue;
+                    }
+                }
+
+                if unchanged {
+                    continue;
+                }
+                self.database
+                    .mappings
+                    .set_at_risk(&source.name, &mapping.linear_issue_id, reason)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The task's planned work blocks in local time, e.g. "Tue 3 Mar 14:00–16:00"
+    fn scheduled_blocks(task: &MotionTask, timezone: Tz) -> Vec<String> {
+        let mut blocks: Vec<_> = task
+            .chunks
+            .iter()
+            .flatten()
+            .filter_map(|chunk| Some((chunk.scheduled_start?, chunk.scheduled_end?)))
+            .collect();
+
+        if blocks.is_empty() {
+            if let (Some(start), Some(end)) = (task.scheduled_start, task.scheduled_end) {
+                blocks.push((start, end));
+            }
+        }
+        blocks.sort();
+
+        blocks
+            .into_iter()
+            .map(|(start, end)| {
+                let start = start.with_timezone(&timezone);
+                let end = end.with_timezone(&timezone);
+                if start.date_naive() == end.date_naive() {
+                    format!(
+                        "{}–{}",
+                        start.format("%a %-d %b %H:%M"),
+                        end.format("%H:%M")
+                    )
+                } else {
+                    format!(
+                        "{} – {}",
+                        start.format("%a %-d %b %H:%M"),
+                        end.format("%a %-d %b %H:%M")
+                    )
+                }
+            })
+            .collect()
+    }
+
+    fn schedule_title(blocks: &[String]) -> String {
+        if blocks.is_empty() {
+            "Not yet scheduled in Motion".to_string()
+        } else {
+            format!("Scheduled in Motion: {}", blocks.join(", "))
+        }
+    }
+
+    fn schedule_comment(blocks: &[String]) -> String {
+        if blocks.is_empty() {
+            return "**Motion schedule:** not yet scheduled".to_string();
+        }
+
+        let lines: Vec<String> = blocks.iter().map(|block| format!("- {}", block)).collect();
+        format!("**Motion schedule:**\n\n{}", lines.join("\n"))
+    }
+
+    /// Propose links between hand-made Motion tasks and assigned Linear issues without a mapping
+    #[tracing::instrument(skip(self, config))]
+    pub async fn propose_adoptions(&self, config: &AppConfig) -> Result<Vec<AdoptionCandidate>> {
+        let workspace = Self::target_workspace(&self.motion_client).await?;
+        let tasks = self.motion_client.list_tasks(&workspace.id).await?;
+
+        let mut issues = Vec::new();
+        for source in &config.sync_sources {
+            let linear_client = LinearClient::new(source.linear_api_key.clone())?;
+            let source_issues = linear_client
+                .get_assigned_issues(source.projects.clone())
+                .await?;
+            for issue in source_issues {
+                let mapping = self
+                    .database
+                    .mappings
+                    .get_mapping_by_linear_id(&source.name, &issue.id)
+                    .await?;
+                if mapping.is_none_or(|mapping| mapping.motion_task_id.is_none()) {
+                    issues.push((source.name.clone(), issue));
+                }
+            }
+        }
+
+        Ok(adopt::propose(&tasks, &issues))
+    }
+
+    /// Label and rename an existing Motion task as synced and map it to the candidate's issue
+    pub async fn adopt(&self, candidate: &AdoptionCandidate) -> Result<()> {
+        let Some(motion_task_id) = candidate.task.id.as_deref() else {
+            return Err(Error::Sync(format!(
+                "Motion task '{}' has no id and can't be adopted",
+                candidate.task.name
+            )));
+        };
+        let mut labels: Vec<String> = candidate
+            .task
+            .labels
+            .iter()
+            .flatten()
+            .map(|label| label.name.clone())
+            .collect();
+        labels.push("linear-sync".to_string());
+
+        // Name it like the tasks we create, so it's recognised as synced straight away
+        let update = TaskUpdate {
+            name: Some(format!(
+                "[{}] {}",
+                candidate.issue.identifier, candidate.issue.title
+            )),
+            labels: Some(labels),
+            ..Default::default()
+        };
+        let task = self
+            .motion_client
+            .update_task_fields(motion_task_id, &update)
+            .await?;
+
+        self.database
+            .mappings
+            .adopt_task(&candidate.sync_source, &candidate.issue, &task)
+            .await?;
+        info!(
+            "🔗 Adopted Motion task {} for {}",
+            motion_task_id, candidate.issue.identifier
+        );
+        Ok(())
+    }
+
+    /// Rebuild mappings from the synced tasks in Motion, e.g. after the database was lost.
+    ///
+    /// Nothing is written in a dry run. Duplicate and orphaned tasks are only deleted
+    /// with `delete`, otherwise they are reported.
+    #[tracing::instrument(skip(self, config))]
+    pub async fn reconcile(
+        &self,
+        config: &AppConfig,
+        dry_run: bool,
+        delete: bool,
+        confirm_deletions: bool,
+    ) -> Result<Vec<ReconcileEntry>> {
+        let workspace = Self::target_workspace(&self.motion_client).await?;
+        let tasks: Vec<MotionTask> = self
+            .motion_client
+            .list_tasks(&workspace.id)
+            .await?
+            .into_iter()
+            .filter(MotionTask::is_synced_task)
+            .collect();
+        info!("Found {} synced tasks in Motion", tasks.len());
+
+        let mut issues = Vec::new();
+        for source in &config.sync_sources {
+            let linear_client = LinearClient::new(source.linear_api_key.clone())?;
+            let source_issues = linear_client
+                .get_assigned_issues(source.projects.clone())
+                .await?;
+            issues.extend(
+                source_issues
+                    .into_iter()
+                    .map(|issue| (source.name.clone(), issue)),
+            );
+        }
+
+        let mappings = self.database.mappings.list_all_mappings().await?;
+        let mut entries = reconcile::plan(&tasks, &issues, &mappings);
+        if dry_run {
+            return Ok(entries);
+        }
+
+        // Hold back a mass deletion the same way cleanup does
+        let deletions = entries
+            .iter()
+            .filter(|entry| {
+                matches!(
+                    entry.action,
+                    ReconcileAction::Duplicate | ReconcileAction::Orphaned
+                )
+            })
+            .count();
+        let guard = &config.global_sync_rules.deletion_guard;
+        let held = delete && !confirm_deletions && !guard.allows(deletions, tasks.len());
+        if held {
+            warn!(
+                "🛑 Holding {} of {} synced Motion tasks back from deletion ({:?}), rerun with --confirm-deletions to delete them",
+                deletions,
+                tasks.len(),
+                guard
+            );
+        }
+        let delete = delete && !held;
+
+        for entry in &mut entries {
+            let result = match entry.action {
+                ReconcileAction::Mapped => continue,
+                ReconcileAction::Adopted => {
+                    let issue = issues.iter().find(|(source, issue)| {
+                        Some(source) == entry.sync_source.as_ref()
+                            && Some(&issue.id) == entry.linear_issue_id.as_ref()
+                    });
+                    let task = tasks
+                        .iter()
+                        .find(|task| task.id.as_deref() == Some(entry.motion_task_id.as_str()));
+                    match (issue, task) {
+                        (Some((source, issue)), Some(task)) => {
+                            self.database.mappings.adopt_task(source, issue, task).await
+                        }
+                        _ => continue,
+                    }
+                }
+                ReconcileAction::Duplicate | ReconcileAction::Orphaned if delete => {
+                    let reason = match entry.action {
+                        ReconcileAction::Duplicate => "Duplicate found by reconcile",
+                        _ => "Orphan found by reconcile",
+                    };
+                    match self
+                        .delete_with_tombstone(&entry.motion_task_id, None, reason)
+                        .await
+                    {
+                        Err(Error::NotFound(_)) => Ok(()),
+                        result => result,
+                    }
+                }
+                ReconcileAction::Duplicate | ReconcileAction::Orphaned => continue,
+            };
+
+            match result {
+                Ok(()) => entry.applied = true,
+                Err(e) => warn!(
+                    "Failed to reconcile Motion task {} ({:?}): {}",
+                    entry.motion_task_id, entry.action, e
+                ),
+            }
+        }
+
+        self.database.flush().await?;
+        Ok(entries)
+    }
+
+    /// Compare every mapped Motion task with the projection of its stored Linear issue.
+    ///
+    /// `mode` overrides the configured drift mode and interval, as used by the `drift` command.
+    #[tracing::instrument(skip(self, config))]
+    pub async fn check_drift(
+        &self,
+        config: &AppConfig,
+        tasks: Option<&HashMap<String, MotionTask>>,
+        mode: Option<DriftMode>,
+        source_filter: Option<&str>,
+    ) -> Result<Vec<DriftReport>> {
+        let timezone = config.timezone();
+        let mut reports = Vec::new();
+
+        let fetched;
+        let tasks = match tasks {
+            Some(tasks) => tasks,
+            None => {
+                let workspace = Self::target_workspace(&self.motion_client).await?;
+                fetched = self.motion_tasks_by_id(&workspace).await?;
+                &fetched
+            }
+        };
+
+        for source in &config.sync_sources {
+            if source_filter.is_some_and(|name| name != source.name) {
+                continue;
+            }
+
+            let sync_rules = source.effective_sync_rules(&config.global_sync_rules);
+            let drift_mode = match mode {
+                Some(mode) => mode,
+                None => {
+                    let check = &sync_rules.drift_check;
+                    if check.mode == DriftMode::Off {
+                        continue;
+                    }
+
+                    let last_check = self
+                        .database
+                        .status
+                        .get_source_status(&source.name)
+                        .await?
+                        .and_then(|status| status.last_drift_check);
+                    let interval = chrono::Duration::hours(check.interval_hours as i64);
+                    if last_check.is_some_and(|last| chrono::Utc::now() - last < interval) {
+                        continue;
+                    }
+                    check.mode
+                }
+            };
+
+            info!("Checking for drift in source '{}'", source.name);
+
+            let mappings = self
+                .database
+                .mappings
+                .list_mappings_by_source(&source.name)
+                .await?;
+
+            for mapping in mappings {
+                if !matches!(mapping.status, MappingStatus::Synced) {
+                    continue;
+                }
+                let (Some(motion_task_id), Ok(issue)) =
+                    (&mapping.motion_task_id, Self::stored_issue(&mapping))
+                else {
+                    continue;
+                };
+
+                let expected = Self::task_projection(
+                    &issue,
+                    &sync_rules,
+                    timezone,
+                    mapping.created_at,
+                    &source.name,
+                );
+                // The listing leaves out completed tasks, only those are fetched one by one
+                let task = match tasks.get(motion_task_id) {
+                    Some(task) => Ok(task.clone()),
+                    None => self.motion_client.get_task(motion_task_id).await,
+                };
+                let (task, found) = match task {
+                    Ok(task) => {
+                        let skip = Self::deliberate_motion_edits(&mapping, &task, &sync_rules);
+                        let found = drift::detect(&expected, &task, &skip);
+                        (Some(task), found)
+                    }
+                    Err(Error::NotFound(_)) => (None, vec![Drift::Missing]),
+                    Err(e) => {
+                        warn!("Failed to fetch Motion task {}: {}", motion_task_id, e);
+                        continue;
+                    }
+                };
+
+                if found.is_empty() {
+                    continue;
+                }
+                for drift in &found {
+                    warn!("🔀 {} has drifted: {}", issue.identifier, drift);
+                }
+
+                let repaired = drift_mode == DriftMode::Repair
+                    && match self
+                        .repair_drift(
+                            source,
+                            &mapping,
+                            task.as_ref(),
+                            &expected,
+                            &found,
+                            &sync_rules,
+                        )
+                        .await
+                    {
+                        Ok(()) => true,
+                        Err(e) => {
+                            warn!("Failed to repair drift on {}: {}", issue.identifier, e);
+                            false
+                        }
+                    };
+
+                reports.push(DriftReport {
+                    sync_source: source.name.clone(),
+                    linear_issue_id: mapping.linear_issue_id.clone(),
+                    identifier: issue.identifier.clone(),
+                    motion_task_id: motion_task_id.clone(),
+                    drift: found,
+                    repaired,
+                });
+            }
+
+            self.database
+                .status
+                .record_drift_check(&source.name)
+                .await?;
+        }
+
+        Ok(reports)
+    }
+
+    /// Fields whose Motion value is kept on purpose: edited in Motion since our last write,
+    /// or left in place by an earlier merge. Under `linear_wins` nothing is kept.
+    fn deliberate_motion_edits(
+        mapping: &crate::db::mapping::TaskMapping,
+        task: &MotionTask,
+        sync_rules: &SyncRules,
+    ) -> Vec<String> {
+        if sync_rules.conflict_policy == ConflictPolicy::LinearWins {
+            return Vec::new();
+        }
+        let Some(snapshot) = &mapping.motion_snapshot else {
+            return Vec::new();
+        };
+
+        let mut fields = snapshot.edited_fields(&MotionSnapshot::from(task));
+        fields.extend(mapping.conflicts.iter().map(|c| c.field.clone()));
+
+        if let Some(base) = &mapping.last_projection {
+            let duration_mins = match base.duration {
+                Some(crate::clients::motion::TaskDuration::Minutes(minutes)) => Some(minutes),
+                _ => None,
+            };
+            let kept = [
+                ("name", base.name.as_ref() != Some(&snapshot.name)),
+                ("duration", duration_mins != snapshot.duration_mins),
+                ("dueDate", base.due_date != snapshot.due_date),
+            ];
+            fields.extend(
+                kept.into_iter()
+                    .filter(|(_, kept)| *kept)
+                    .map(|(field, _)| field.to_string()),
+            );
+        }
+
+        fields
+    }
+
+    /// Write the expected values for drifted fields back to Motion
+    async fn repair_drift(
+        &self,
+        source: &SyncSource,
+        mapping: &crate::db::mapping::TaskMapping,
+        task: Option<&MotionTask>,
+        expected: &TaskUpdate,
+        found: &[Drift],
+        sync_rules: &SyncRules,
+    ) -> Result<()> {
+        let Some(task) = task else {
+            // The task is gone, handle it like a deletion noticed during sync
+            let policy = sync_rules.deleted_task_policy;
+            if policy == DeletedTaskPolicy::Label {
+                let linear_client = LinearClient::new(source.linear_api_key.clone())?;
+                if let Err(e) = linear_client
+                    .add_label_to_issue(&mapping.linear_issue_id, &sync_rules.deleted_task_label)
+                    .await
+                {
+                    warn!(
+                        "Failed to label {} with '{}': {}",
+                        mapping.linear_issue_id, sync_rules.deleted_task_label, e
+                    );
+                }
+            }
+            return self
+                .database
+                .mappings
+                .mark_deleted_downstream(
+                    &mapping.sync_source,
+                    &mapping.linear_issue_id,
+                    Self::deletion_outcome(policy),
+                )
+                .await;
+        };
+
+        let drifted: Vec<String> = found
+            .iter()
+            .filter_map(Drift::field)
+            .map(str::to_string)
+            .collect();
+        let others: Vec<String> = expected
+            .fields()
+            .into_iter()
+            .filter(|field| !drifted.contains(field))
+            .collect();
+        let mut update = expected.without(&others)?;
+
+        // Add missing labels without dropping ones added in Motion
+        if let Some(labels) = &mut update.labels {
+            for label in task.labels.iter().flatten() {
+                if !labels.contains(&label.name) {
+                    labels.push(label.name.clone());
+                }
+            }
+        }
+
+        let motion_task_id = task.id.as_deref().unwrap_or_default();
+        let updated_task = self
+            .motion_client
+            .update_task_fields(motion_task_id, &update)
+            .await?;
+        info!(
+            "Repaired drift on Motion task {}: {:?}",
+            motion_task_id,
+            update.fields()
+        );
+
+        self.database
+            .mappings
+            .set_motion_snapshot(
+                &mapping.sync_source,
+                &mapping.linear_issue_id,
+                &updated_task,
+            )
+            .await?;
+        self.database
+            .mappings
+            .record_projection(
+                &mapping.sync_source,
+                &mapping.linear_issue_id,
+                expected.clone(),
+                &update,
+                Vec::new(),
+            )
+            .await
+    }
+
+    /// Clean up Motion tasks for Linear issues that are no longer assigned to the user.
+    ///
+    /// Issues that left the assigned set because they reached a terminal state (done,
+    /// canceled, duplicate) get the source's terminal action; all others are deleted.
+    /// Deletions above the source's deletion guard are held unless `confirm_deletions` is set.
+    #[tracing::instrument(skip(self, config))]
+    pub async fn cleanup_unassigned_tasks(
+        &self,
+        config: &AppConfig,
+        confirm_deletions: bool,
+    ) -> Result<()> {
+        info!("Cleaning up Motion tasks for unassigned Linear issues");
+
+        for source in &config.sync_sources {
+            debug!("Checking for orphaned tasks from source: {}", source.name);
+
+            // Get all mappings for this source
+            let existing_mappings = self
+                .database
+                .mappings
+                .list_mappings_by_source(&source.name)
+                .await?;
+
+            if existing_mappings.is_empty() {
+                debug!("No existing mappings found for source: {}", source.name);
+                continue;
+            }
+
+            // Get currently assigned issues from Linear
+            let linear_client = LinearClient::new(source.linear_api_key.clone())?;
+            let current_issues = match linear_client.get_assigned_issues(source.projects.clone()).await {
+                Ok(issues) => issues,
+                Err(e) => {
+                    error!("Failed to fetch current issues for source '{}': {}", source.name, e);
+                    continue; // Skip this source if we can't fetch current issues
+                }
+            };
+
+            // Create a set of currently assigned issue IDs for quick lookup
+            let current_issue_ids: std::collections::HashSet<String> = 
+                current_issues.iter().map(|issue| issue.id.clone()).collect();
+
+            // Release held deletions for issues that are assigned again
+            for mapping in &existing_mappings {
+                if mapping.deletion_held_since.is_some()
+                    && current_issue_ids.contains(&mapping.linear_issue_id)
+                {
+                    self.database
+                        .mappings
+                        .set_deletion_held(&source.name, &mapping.linear_issue_id, false)
+                        .await?;
+                }
+            }
+
+            // Find orphaned mappings (exist in DB but issue is no longer assigned), skipping
+            // completed mappings that already recorded the issue's terminal state
+            let orphaned_mappings: Vec<&crate::db::mapping::TaskMapping> = existing_mappings
+                .iter()
+                .filter(|mapping| !current_issue_ids.contains(&mapping.linear_issue_id))
+                .filter(|mapping| {
+                    !(matches!(mapping.status, MappingStatus::Completed)
+                        && Self::stored_issue(mapping).is_ok_and(|i| i.state.is_terminal()))
+                })
+                .collect();
+
+            if orphaned_mappings.is_empty() {
+                continue;
+            }
+
+            info!(
+                "Found {} orphaned mappings for source '{}' (out of {} total mappings)",
+                orphaned_mappings.len(),
+                source.name,
+                existing_mappings.len()
+            );
+
+            // Look up the orphaned issues to tell terminal transitions from reassignments
+            let orphaned_ids: Vec<String> = orphaned_mappings
+                .iter()
+                .map(|mapping| mapping.linear_issue_id.clone())
+                .collect();
+            let orphaned_issues: HashMap<String, LinearIssue> =
+                match linear_client.get_issues_by_ids(&orphaned_ids).await {
+                    Ok(issues) => issues
+                        .into_iter()
+                        .map(|issue| (issue.id.clone(), issue))
+                        .collect(),
+                    Err(e) => {
+                        error!(
+                            "Failed to fetch orphaned issues for source '{}': {}",
+                            source.name, e
+                        );
+                        continue;
+                    }
+                };
+
+            let sync_rules = source.effective_sync_rules(&config.global_sync_rules);
+
+            // A partial Linear response looks like mass unassignment, so guard the deletions
+            let deletions = orphaned_mappings
+                .iter()
+                .filter(|mapping| Self::cleanup_deletes(mapping, &orphaned_issues))
+                .count();
+            let synced_tasks = existing_mappings
+                .iter()
+                .filter(|mapping| {
+                    mapping.motion_task_id.is_some()
+                        && !matches!(
+                            mapping.status,
+                            MappingStatus::Completed | MappingStatus::Ignored
+                        )
+                })
+                .count();
+            let hold_deletions =
+                !confirm_deletions && !sync_rules.deletion_guard.allows(deletions, synced_tasks);
+            if hold_deletions {
+                warn!(
+                    "🛑 Holding {} of {} Motion task deletions for '{}' ({:?}), confirm with `sync --confirm-deletions`",
+                    deletions, synced_tasks, source.name, sync_rules.deletion_guard
+                );
+            }
+
+            // Resolve Motion tasks and remove mappings for orphaned items
+            for mapping in orphaned_mappings {
+                let terminal_issue = orphaned_issues
+                    .get(&mapping.linear_issue_id)
+                    .filter(|issue| issue.state.is_terminal());
+
+                if hold_deletions && Self::cleanup_deletes(mapping, &orphaned_issues) {
+                    self.database
+                        .mappings
+                        .set_deletion_held(&source.name, &mapping.linear_issue_id, true)
+                        .await?;
+                    continue;
+                }
+
+                if matches!(mapping.status, MappingStatus::Completed) {
+                    // The Motion task is already done, only keep the stored state current
+                    let result = match terminal_issue {
+                        Some(issue) => {
+                            self.database
+                                .mappings
+                                .mark_completed(&source.name, &mapping.linear_issue_id, Some(issue))
+                                .await
+                        }
+                        None => self
+                            .database
+                            .mappings
+                            .remove_mapping(&source.name, &mapping.linear_issue_id)
+                            .await
+                            .map(|_| ()),
+                    };
+                    if let Err(e) = result {
+                        error!(
+                            "Failed to update completed mapping for {}: {}",
+                            mapping.linear_issue_id, e
+                        );
+                    }
+                    continue;
+                }
+
+                if let Some(motion_task_id) = &mapping.motion_task_id {
+                    let result = match terminal_issue {
+                        Some(issue) => {
+                            let action = Self::terminal_action(issue, &sync_rules);
+                            info!(
+                                "Linear issue {} moved to '{}', applying {:?} to Motion task {}",
+                                issue.identifier, issue.state.name, action, motion_task_id
+                            );
+                            self.apply_terminal_action(mapping, action, &sync_rules)
+                                .await
+                        }
+                        None => {
+                            debug!(
+                                "Deleting Motion task {} for unassigned Linear issue {}",
+                                motion_task_id, mapping.linear_issue_id
+                            );
+                            self.delete_with_tombstone(
+                                motion_task_id,
+                                Some(mapping),
+                                "Linear issue is no longer assigned",
+                            )
+                            .await
+                        }
+                    };
+
+                    // A task that is already gone from Motion needs no further action
+                    let result = match result {
+                        Err(Error::NotFound(_)) => Ok(()),
+                        result => result,
+                    };
+
+                    match result {
+                        Ok(()) => {
+                            info!(
+                                "✅ Resolved Motion task {} for orphaned issue {}",
+                                motion_task_id, mapping.linear_issue_id
+                            );
+
+                            // Keep terminal mappings so a reopened issue can be detected
+                            let result = match terminal_issue {
+                                Some(issue) => {
+                                    self.database
+                                        .mappings
+                                        .mark_completed(
+                                            &source.name,
+                                            &mapping.linear_issue_id,
+                                            Some(issue),
+                                        )
+                                        .await
+                                }
+                                None => self
+                                    .database
+                                    .mappings
+                                    .remove_mapping(&source.name, &mapping.linear_issue_id)
+                                    .await
+                                    .map(|_| ()),
+                            };
+
+                            if let Err(e) = result {
+                                error!(
+                                    "Failed to update mapping for {}: {}",
+                                    mapping.linear_issue_id, e
+                                );
+                            } else {
+                                debug!(
+                                    "Updated mapping for orphaned issue: {}",
+                                    mapping.linear_issue_id
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            error!(
+                                "❌ Failed to resolve Motion task {} for orphaned issue {}: {}",
+                                motion_task_id, mapping.linear_issue_id, e
+                            );
+                            self.database
+                                .history
+                                .record(SyncEvent::new(
+                                    mapping,
+                                    SyncEventKind::Error {
+                                        message: e.to_string(),
+                                    },
+                                ))
+                                .await?;
+                            // Don't remove the mapping if we couldn't resolve the Motion task
+                        }
+                    }
+                } else {
+                    // Mapping exists but no Motion task was created yet - just remove the mapping
+                    debug!(
+                        "Removing orphaned mapping without Motion task for issue: {}",
+                        mapping.linear_issue_id
+                    );
+
+                    if let Err(e) = self
+                        .database
+                        .mappings
+                        .remove_mapping(&source.name, &mapping.linear_issue_id)
+                        .await
+                    {
+                        error!(
+                            "Failed to remove orphaned mapping for {}: {}",
+                            mapping.linear_issue_id, e
+                        );
+                    }
+                }
+            }
+        }
+
+        info!("Cleanup of unassigned tasks completed");
+        Ok(())
+    }
+
+    /// Delete a Motion task, keeping a tombstone of it and its mapping so it can be restored
+    async fn delete_with_tombstone(
+        &self,
+        motion_task_id: &str,
+        mapping: Option<&crate::db::mapping::TaskMapping>,
+        reason: &str,
+    ) -> Result<()> {
+        let task = self.motion_client.get_task(motion_task_id).await?;
+        let tombstone = Tombstone {
+            motion_task: task,
+            mapping: mapping.cloned(),
+            reason: reason.to_string(),
+            deleted_at: chrono::Utc::now(),
+        };
+        self.database.mappings.store_tombstone(&tombstone).await?;
+
+        if let Err(e) = self.motion_client.delete_task(motion_task_id).await {
+            self.database
+                .mappings
+                .remove_tombstone(motion_task_id)
+                .await?;
+            return Err(e);
+        }
+
+        if let Some(mapping) = mapping {
+            self.database
+                .history
+                .record(SyncEvent::new(
+                    mapping,
+                    SyncEventKind::Deleted {
+                        reason: reason.to_string(),
+                    },
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Recreate a Motion task from its tombstone, found by Motion task id or Linear identifier,
+    /// and link it to its mapping again
+    #[tracing::instrument(skip(self, config))]
+    pub async fn restore(&self, config: &AppConfig, target: &str) -> Result<MotionTask> {
+        let tombstone = match self.database.mappings.get_tombstone(target).await? {
+            Some(tombstone) => tombstone,
+            None => self
+                .database
+                .mappings
+                .list_tombstones()
+                .await?
+                .into_iter()
+                .find(|tombstone| {
+                    tombstone
+                        .identifier()
+                        .is_some_and(|identifier| identifier.eq_ignore_ascii_case(target))
+                })
+                .ok_or_else(|| Error::NotFound(format!("No tombstone for '{}'", target)))?,
+        };
+
+        // Don't create a second task for an issue that was synced again since
+        let mapping = match &tombstone.mapping {
+            Some(old) => {
+                let current = self
+                    .database
+                    .mappings
+                    .get_mapping_by_linear_id(&old.sync_source, &old.linear_issue_id)
+                    .await?;
+                if let Some(task_id) = current.as_ref().and_then(|m| m.motion_task_id.as_ref()) {
+                    return Err(Error::Validation(format!(
+                        "Linear issue {} is already linked to Motion task {}",
+                        old.linear_issue_id, task_id
+                    )));
+                }
+                Some(current.unwrap_or_else(|| old.clone()))
+            }
+            None => None,
+        };
+
+        // Cleanup would delete a restored task again unless its issue is still assigned and open
+        if let Some(mapping) = &mapping {
+            let source = config
+                .sync_sources
+                .iter()
+                .find(|source| source.name == mapping.sync_source)
+                .ok_or_else(|| {
+                    Error::Validation(format!(
+                        "Sync source '{}' is no longer configured",
+                        mapping.sync_source
+                    ))
+                })?;
+            let linear_client = LinearClient::new(source.linear_api_key.clone())?;
+            let assigned = linear_client
+                .get_assigned_issues(source.projects.clone())
+                .await?
+                .iter()
+                .any(|issue| issue.id == mapping.linear_issue_id);
+            if !assigned {
+                return Err(Error::Validation(format!(
+                    "Linear issue {} is no longer assigned or is closed, the next sync would delete the task again",
+                    mapping
+                        .identifier()
+                        .unwrap_or_else(|| mapping.linear_issue_id.clone())
+                )));
+            }
+        }
+
+        let task = MotionTask {
+            id: None,
+            ..tombstone.motion_task.clone()
+        };
+        let restored = self.motion_client.create_task(&task).await?;
+
+        if let Some(mapping) = mapping {
+            self.database
+                .mappings
+                .relink_restored(mapping, &restored)
+                .await?;
+        }
+        self.database
+            .mappings
+            .remove_tombstone(tombstone.motion_task_id())
+            .await?;
+        self.database.flush().await?;
+
+        info!(
+            "♻️  Restored Motion task {} as {}",
+            tombstone.motion_task_id(),
+            restored.id.as_deref().unwrap_or("unknown")
+        );
+        Ok(restored)
+    }
+
+    /// Sync an issue again after its Motion task was deleted in Motion and it was ignored since
+    pub async fn resume(&self, config: &AppConfig, identifier: &str) -> Result<TaskMapping> {
+        let mapping = self
+            .database
+            .mappings
+            .list_mappings_by_status(MappingStatus::Ignored)
+            .await?
+            .into_iter()
+            .find(|mapping| {
+                mapping
+                    .identifier()
+                    .is_some_and(|id| id.eq_ignore_ascii_case(identifier))
+            })
+            .ok_or_else(|| Error::NotFound(format!("No ignored issue '{}'", identifier)))?;
+
+        // Take back the label the `label` policy added
+        let labelled = mapping
+            .downstream_deletion
+            .as_ref()
+            .is_some_and(|deletion| deletion.outcome == DeletionOutcome::Label);
+        let source = config
+            .sync_sources
+            .iter()
+            .find(|source| source.name == mapping.sync_source);
+        if let (true, Some(source)) = (labelled, source) {
+            let sync_rules = source.effective_sync_rules(&config.global_sync_rules);
+            let linear_client = LinearClient::new(source.linear_api_key.clone())?;
+            if let Err(e) = linear_client
+                .remove_label_from_issue(&mapping.linear_issue_id, &sync_rules.deleted_task_label)
+                .await
+            {
+                warn!(
+                    "Failed to remove '{}' from {}: {}",
+                    sync_rules.deleted_task_label, identifier, e
+                );
+            }
+        }
+
+        self.database
+            .mappings
+            .resume_ignored(&mapping.sync_source, &mapping.linear_issue_id)
+            .await?;
+        info!("{} will get a new Motion task on the next sync", identifier);
+        Ok(mapping)
+    }
+
+    /// The workspace tasks are created in: "My Private Workspace" or the first available one
+    async fn target_workspace(motion_client: &MotionClient) -> Result<MotionWorkspace> {
+        let workspaces = motion_client.list_workspaces().await?;
+
+        workspaces
+            .iter()
+            .find(|w| w.name == "My Private Workspace")
+            .or_else(|| workspaces.first())
+            .cloned()
+            .ok_or_else(|| Error::MotionApi {
+                message: "No Motion workspaces found".to_string(),
+            })
+    }
+
+    /// Check that every Motion status in the status map and terminal rules exists in the workspace
+    fn validate_statuses(workspace: &MotionWorkspace, sync_rules: &SyncRules) -> Result<()> {
+        let Some(statuses) = &workspace.statuses else {
+            warn!(
+                "Motion workspace '{}' did not return its statuses, skipping status map validation",
+                workspace.name
+            );
+            return Ok(());
+        };
+
+        let terminal = &sync_rules.terminal_states;
+        let actions = [terminal.completed, terminal.canceled, terminal.duplicate];
+        let completed_status = actions
+            .contains(&TerminalAction::Complete)
+            .then_some(COMPLETED_STATUS);
+        let archive_status = actions
+            .contains(&TerminalAction::Archive)
+            .then_some(terminal.archive_status.as_str());
+
+        for status in sync_rules
+            .status_map
+            .motion_statuses()
+            .chain(completed_status)
+            .chain(archive_status)
+        {
+            if !statuses.iter().any(|s| s.name == status) {
+                return Err(Error::Validation(format!(
+                    "Motion status '{}' in sync rules does not exist in workspace '{}'. Available statuses: {}",
+                    status,
+                    workspace.name,
+                    statuses
+                        .iter()
+                        .map(|s| s.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The mapped Motion status for the issue's workflow state, "Todo" if it isn't mapped
+    fn motion_status(
+        issue: &crate::clients::linear::LinearIssue,
+        sync_rules: &SyncRules,
+    ) -> Status {
+        let name = sync_rules
+            .status_map
+            .resolve(&issue.state.name, &issue.state.state_type)
+            .unwrap_or("Todo");
+        Status {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Label, move and/or comment on a Linear issue whose Motion task was completed.
+    ///
+    /// The comment goes last so a retry after a partial failure doesn't post it twice.
+    async fn apply_completion_action(
+        linear_client: &LinearClient,
+        issue_id: &str,
+        sync_rules: &SyncRules,
+    ) -> Result<()> {
+        let action = &sync_rules.completion_action;
+        let completion_tag = &sync_rules.completed_linear_tag;
+
+        if action.add_label
+            && !linear_client
+                .check_issue_has_label(issue_id, completion_tag)
+                .await?
+        {
+            linear_client
+                .add_label_to_issue(issue_id, completion_tag)
+                .await?;
+            debug!("Tagged Linear issue {} with '{}'", issue_id, completion_tag);
+        }
+
+        if let Some(state_name) = &action.move_to_state {
+            linear_client
+                .move_issue_to_state(issue_id, state_name)
+                .await?;
+            debug!("Moved Linear issue {} to '{}'", issue_id, state_name);
+        }
+
+        if let Some(comment) = &action.comment {
+            linear_client.create_comment(issue_id, comment).await?;
+            debug!("Commented on Linear issue {}", issue_id);
+        }
+
+        Ok(())
+    }
+
+    /// Whether cleanup deletes the Motion task of an orphaned mapping, as opposed to applying a
+    /// terminal action or only updating the stored state. Only these deletions are guarded.
+    fn cleanup_deletes(
+        mapping: &TaskMapping,
+        orphaned_issues: &HashMap<String, LinearIssue>,
+    ) -> bool {
+        !matches!(mapping.status, MappingStatus::Completed)
+            && mapping.motion_task_id.is_some()
+            && !orphaned_issues
+                .get(&mapping.linear_issue_id)
+                .is_some_and(|issue| issue.state.is_terminal())
+    }
+
+    /// How a mapping is recorded when its Motion task was deleted outside of the sync
+    fn deletion_outcome(policy: DeletedTaskPolicy) -> DeletionOutcome {
+        match policy {
+            DeletedTaskPolicy::Recreate => DeletionOutcome::Recreate,
+            DeletedTaskPolicy::Ignore => DeletionOutcome::Ignore,
+            DeletedTaskPolicy::Label => DeletionOutcome::Label,
+        }
+    }
+
+    /// The configured action for an issue in a terminal state
+    fn terminal_action(issue: &LinearIssue, sync_rules: &SyncRules) -> TerminalAction {
+        let rules = &sync_rules.terminal_states;
+
+        if issue.state.is_duplicate() {
+            rules.duplicate
+        } else if issue.state.state_type == "completed" {
+            rules.completed
+        } else {
+            rules.canceled
+        }
+    }
+
+    async fn apply_terminal_action(
+        &self,
+        mapping: &crate::db::mapping::TaskMapping,
+        action: TerminalAction,
+        sync_rules: &SyncRules,
+    ) -> Result<()> {
+        let motion_task_id = mapping.motion_task_id.as_deref().unwrap_or_default();
+        match action {
+            TerminalAction::Delete => {
+                self.delete_with_tombstone(
+                    motion_task_id,
+                    Some(mapping),
+                    "Linear issue reached a terminal state",
+                )
+                .await
+            }
+            TerminalAction::Complete => {
+                self.motion_client
+                    .mark_task_completed(motion_task_id)
+                    .await?;
+                self.database
+                    .history
+                    .record(SyncEvent::new(
+                        mapping,
+                        SyncEventKind::Completed {
+                            reason: "Linear issue reached a terminal state".to_string(),
+                        },
+                    ))
+                    .await
+            }
+            TerminalAction::Archive => {
+                self.motion_client
+                    .set_task_status(motion_task_id, &sync_rules.terminal_states.archive_status)
+                    .await?;
+                self.database
+                    .history
+                    .record(SyncEvent::new(
+                        mapping,
+                        SyncEventKind::Completed {
+                            reason: format!(
+                                "Linear issue reached a terminal state, Motion task moved to '{}'",
+                                sync_rules.terminal_states.archive_status
+                            ),
+                        },
+                    ))
+                    .await
+            }
+        }
+    }
+
+    /// Format the description with a link back to the Linear issue
+    fn format_description_with_link(
+        issue: &crate::clients::linear::LinearIssue,
+        sync_source_name: &str,
+    ) -> Option<String> {
+        let base_description = issue.description.as_ref().cloned().unwrap_or_default();
+        let linear_link = format!(
+            "https://linear.app/{}/issue/{}",
+            sync_source_name, issue.identifier
+        );
+
+        if base_description.is_empty() {
+            Some(format!("Linear: {}", linear_link))
+        } else {
+            Some(format!("{}\n\nLinear: {}", base_description, linear_link))
+        }
+    }
+
+    /// Calculate the task duration from the Linear estimate using the team's estimation scale
+    fn duration_mins(issue: &crate::clients::linear::LinearIssue, sync_rules: &SyncRules) -> u32 {
+        let strategy = &sync_rules.time_estimate_strategy;
+
+        let duration = match (issue.estimate, issue.team.estimation_scale()) {
+            (Some(estimate), Some(scale)) => strategy.convert_estimate_for_scale(estimate, &scale),
+            // Without a known scale, fall back to the value-based lookup
+            (Some(estimate), None) => strategy.convert_estimate_by_value(estimate),
+            (None, _) => None,
+        };
+
+        duration.unwrap_or(sync_rules.default_task_duration_mins)
+    }
+
+    /// The Motion deadline: the Linear due date in the configured timezone, or the
+    /// configured default deadline policy for issues without one
+    fn deadline(
+        issue: &crate::clients::linear::LinearIssue,
+        sync_rules: &SyncRules,
+        timezone: Tz,
+        first_synced_at: chrono::DateTime<chrono::Utc>,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        if let Some(due_date) = issue.due_date.as_deref() {
+            return sync_rules.deadline_for_due_date(due_date, timezone);
+        }
+
+        let inputs = DeadlineInputs {
+            anchor: first_synced_at,
+            priority: issue.priority,
+            cycle_ends_at: issue.cycle.as_ref().map(|c| c.ends_at),
+            project_target_date: issue
+                .project
+                .as_ref()
+                .and_then(|p| p.target_date.as_deref()),
+        };
+
+        sync_rules.default_deadline(&inputs, timezone)
+    }
+
+    /// The cycle start for issues whose cycle starts in the future, if they should be held back
+    fn held_until(
+        issue: &crate::clients::linear::LinearIssue,
+        sync_rules: &SyncRules,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        if !sync_rules.cycles.hold_until_cycle_start {
+            return None;
+        }
+
+        issue
+            .cycle
+            .as_ref()
+            .map(|c| c.starts_at)
+            .filter(|starts_at| *starts_at > chrono::Utc::now())
+    }
+
+    /// The auto-scheduling settings, starting at the issue's cycle start when configured and
+    /// otherwise when the issue was first synced, so leaving a cycle resets the start date
+    fn auto_scheduled(
+        issue: &crate::clients::linear::LinearIssue,
+        sync_rules: &SyncRules,
+        due_date: Option<chrono::DateTime<chrono::Utc>>,
+        first_synced_at: chrono::DateTime<chrono::Utc>,
+    ) -> AutoScheduled {
+        let cycle_start = issue
+            .cycle
+            .as_ref()
+            .filter(|_| sync_rules.cycles.start_on_cycle_start)
+            .map(|c| c.starts_at);
+
+        AutoScheduled {
+            deadline_type: if due_date.is_some() { "SOFT" } else { "NONE" }.to_string(),
+            schedule: "Work hours".to_string(),
+            start_date: Some(cycle_start.unwrap_or(first_synced_at)),
+        }
+    }
+
+    /// Convert the Linear priority (0-4) to a Motion priority using the configured map
+    fn motion_priority(
+        issue: &crate::clients::linear::LinearIssue,
+        sync_rules: &SyncRules,
+    ) -> String {
+        sync_rules
+            .priority_map
+            .resolve(issue.priority, issue.labels.iter().map(|l| l.name.as_str()))
+    }
+
+    /// Push the fields enabled in `reverse_sync` that were edited in Motion since our last write
+    /// back to Linear, returning the issue as it now stands in Linear.
+    ///
+    /// A field that also changed in Linear is left for the forward sync to overwrite.
+    async fn push_motion_edits(
+        database: &SyncDatabase,
+        linear_client: &LinearClient,
+        mapping: &crate::db::mapping::TaskMapping,
+        issue: &LinearIssue,
+        task: &MotionTask,
+        sync_rules: &SyncRules,
+        timezone: Tz,
+    ) -> Result<Option<LinearIssue>> {
+        let Some(snapshot) = &mapping.motion_snapshot else {
+            // Nothing to compare against yet, record a baseline for later edits
+            database
+                .mappings
+                .set_motion_snapshot(&mapping.sync_source, &issue.id, task)
+                .await?;
+            return Ok(None);
+        };
+
+        if task.updated_time == snapshot.updated_time {
+            return Ok(None);
+        }
+
+        let stored_issue = Self::stored_issue(mapping)?;
+        let current = MotionSnapshot::from(task);
+        let reverse = &sync_rules.reverse_sync;
+        let mut update = IssueUpdate::default();
+        let mut updated_issue = issue.clone();
+
+        if reverse.duration && current.duration_mins != snapshot.duration_mins {
+            let scale = issue.team.estimation_scale();
+            if stored_issue.estimate != issue.estimate {
+                debug!(
+                    "{} estimate changed in both, keeping Linear's",
+                    issue.identifier
+                );
+            } else if let (Some(minutes), Some(scale)) = (current.duration_mins, scale) {
+                let estimate = sync_rules
+                    .time_estimate_strategy
+                    .estimate_for_duration(minutes, &scale);
+                if estimate.is_some() && estimate != issue.estimate {
+                    update.estimate = estimate.map(|e| e as u32);
+                    updated_issue.estimate = estimate;
+                }
+            }
+        }
+
+        if reverse.priority && current.priority != snapshot.priority {
+            if stored_issue.priority != issue.priority {
+                debug!(
+                    "{} priority changed in both, keeping Linear's",
+                    issue.identifier
+                );
+            } else if let Some(priority) = current
+                .priority
+                .as_deref()
+                .and_then(|p| sync_rules.priority_map.linear_priority(p))
+            {
+                if Some(priority) != issue.priority {
+                    update.priority = Some(priority);
+                    updated_issue.priority = Some(priority);
+                }
+            }
+        }
+
+        if reverse.due_date && current.due_date != snapshot.due_date {
+            if stored_issue.due_date != issue.due_date {
+                debug!(
+                    "{} due date changed in both, keeping Linear's",
+                    issue.identifier
+                );
+            } else if let Some(due_date) = current.due_date {
+                let due_date = due_date
+                    .with_timezone(&timezone)
+                    .format("%Y-%m-%d")
+                    .to_string();
+                if Some(&due_date) != issue.due_date.as_ref() {
+                    update.due_date = Some(due_date.clone());
+                    updated_issue.due_date = Some(due_date);
+                }
+            }
+        }
+
+        // Nothing pushed, so the snapshot keeps the edits for the forward merge to see
+        if update.is_empty() {
+            return Ok(None);
+        }
+
+        info!(
+            "Pushing Motion edits for {} to Linear: {:?}",
+            issue.identifier, update
+        );
+        updated_issue.updated_at = linear_client.update_issue(&issue.id, &update).await?;
+
+        // Record both sides as in sync so the push isn't mistaken for a Linear change
+        database
+            .mappings
+            .update_issue_data(&mapping.sync_source, &issue.id, &updated_issue)
+            .await?;
+        let pushed: Vec<String> = [
+            ("duration", update.estimate.is_some()),
+            ("priority", update.priority.is_some()),
+            ("dueDate", update.due_date.is_some()),
+        ]
+        .into_iter()
+        .filter(|(_, pushed)| *pushed)
+        .map(|(field, _)| field.to_string())
+        .collect();
+        if let Some(base) = &mapping.last_projection {
+            let projection = Self::task_projection(
+                &updated_issue,
+                sync_rules,
+                timezone,
+                mapping.created_at,
+                &mapping.sync_source,
+            );
+
+            database
+                .mappings
+                .record_projection(
+                    &mapping.sync_source,
+                    &issue.id,
+                    base.with_fields_from(&projection, &pushed)?,
+                    &TaskUpdate::default(),
+                    Vec::new(),
+                )
+                .await?;
+        }
+        // Only the pushed fields are in sync now, other edits stay visible to the merge
+        if let Some(mut stored) = database
+            .mappings
+            .get_mapping_by_linear_id(&mapping.sync_source, &issue.id)
+            .await?
+        {
+            stored.motion_snapshot = Some(snapshot.with_fields_from(&current, &pushed));
+            database.mappings.update_mapping(stored).await?;
+        }
+
+        Ok(Some(updated_issue))
+    }
+
+    /// Parse the Linear issue data stored in a mapping
+    fn stored_issue(mapping: &crate::db::mapping::TaskMapping) -> Result<LinearIssue> {
+        serde_json::from_value(mapping.linear_issue_data.clone()).map_err(Error::Json)
+    }
+
+    /// Whether a completed issue has moved out of a terminal state since it was last seen
+    fn was_reopened(
+        mapping: &crate::db::mapping::TaskMapping,
+        current_issue: &LinearIssue,
+    ) -> Result<bool> {
+        let stored_issue = Self::stored_issue(mapping)?;
+        Ok(stored_issue.state.is_terminal() && !current_issue.state.is_terminal())
+    }
+
+    /// Check if a Linear issue has changes that require updating the Motion task
+    fn issue_needs_update(
+        mapping: &crate::db::mapping::TaskMapping,
+        current_issue: &crate::clients::linear::LinearIssue,
+    ) -> Result<bool> {
+        // Parse the stored issue data from the mapping
+        let stored_issue = Self::stored_issue(mapping)?;
+
+        // Compare key fields that affect Motion tasks
+        let needs_update = stored_issue.title != current_issue.title
+            || stored_issue.description != current_issue.description
             || stored_issue.estimate != current_issue.estimate
             || stored_issue.priority != current_issue.priority
             || stored_issue.due_date != current_issue.due_date
+            || Self::deadline_inputs_changed(&stored_issue, current_issue)
             || stored_issue.updated_at != current_issue.updated_at;
 
         if needs_update {
@@ -620,125 +2488,139 @@ impl SyncOrchestrator {
         Ok(needs_update)
     }
 
-    async fn update_motion_task_from_issue(
-        motion_client: &MotionClient,
-        motion_task_id: &str,
-        issue: &crate::clients::linear::LinearIssue,
-        sync_rules: &SyncRules,
-        sync_source_name: &str,
-    ) -> Result<MotionTask> {
-        // Calculate duration from Linear estimate
-        let duration_mins = if let Some(estimate) = issue.estimate {
-            sync_rules
-                .time_estimate_strategy
-                .convert_estimate_by_value(estimate)
-                .unwrap_or(sync_rules.default_task_duration_mins)
-        } else {
-            sync_rules.default_task_duration_mins
+    /// Check whether the issue's cycle or the inputs to the default deadline policies have changed
+    fn deadline_inputs_changed(
+        stored_issue: &crate::clients::linear::LinearIssue,
+        current_issue: &crate::clients::linear::LinearIssue,
+    ) -> bool {
+        let cycle = |issue: &crate::clients::linear::LinearIssue| {
+            issue
+                .cycle
+                .as_ref()
+                .map(|c| (c.id.clone(), c.starts_at, c.ends_at))
         };
-
-        // Convert priority (Linear uses 0-4, Motion uses ASAP/HIGH/MEDIUM/LOW)
-        let priority = match issue.priority {
-            Some(1) => Some("ASAP".to_string()),
-            Some(2) => Some("HIGH".to_string()),
-            Some(3) => Some("MEDIUM".to_string()),
-            Some(4) | Some(_) => Some("LOW".to_string()),
-            None => Some("MEDIUM".to_string()),
+        let target_date = |issue: &crate::clients::linear::LinearIssue| {
+            issue.project.as_ref().and_then(|p| p.target_date.clone())
         };
 
-        // Convert due_date string to DateTime if present
-        let due_date = issue.due_date.as_ref().and_then(|date_str| {
-            // Linear sends dates as YYYY-MM-DD, convert to DateTime
-            chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-                .ok()
-                .map(|date| date.and_hms_opt(23, 59, 59).unwrap().and_utc())
-        });
+        cycle(stored_issue) != cycle(current_issue)
+            || target_date(stored_issue) != target_date(current_issue)
+    }
 
-        // Create Motion task object with the updates
-        let motion_task = MotionTask {
-            id: Some(motion_task_id.to_string()),
-            name: format!("[{}] {}", issue.identifier, issue.title),
+    /// The Motion fields computed from a Linear issue, as sent when updating a task
+    fn task_projection(
+        issue: &LinearIssue,
+        sync_rules: &SyncRules,
+        timezone: Tz,
+        first_synced_at: chrono::DateTime<chrono::Utc>,
+        sync_source_name: &str,
+    ) -> TaskUpdate {
+        let due_date = Self::deadline(issue, sync_rules, timezone, first_synced_at);
+
+        TaskUpdate {
+            name: Some(format!("[{}] {}", issue.identifier, issue.title)),
             description: Self::format_description_with_link(issue, sync_source_name),
             duration: Some(crate::clients::motion::TaskDuration::from_minutes(
-                duration_mins,
+                Self::duration_mins(issue, sync_rules),
             )),
-            priority,
+            priority: Some(Self::motion_priority(issue, sync_rules)),
             due_date,
-            labels: Some(vec![Label {
-                name: "linear-sync".to_string(),
-            }]),
+            status: Some(Self::motion_status(issue, sync_rules).name),
+            labels: Some(vec!["linear-sync".to_string()]),
+            auto_scheduled: Some(Self::auto_scheduled(
+                issue,
+                sync_rules,
+                due_date,
+                first_synced_at,
+            )),
             ..Default::default()
+        }
+    }
+
+    /// Three-way merge of the new projection against the last one we wrote and the task as it
+    /// is in Motion: only fields that changed in Linear are written, and those also edited in
+    /// Motion are resolved by the conflict policy.
+    fn merge_update(
+        projection: &TaskUpdate,
+        mapping: &crate::db::mapping::TaskMapping,
+        motion_task: Option<&MotionTask>,
+        policy: ConflictPolicy,
+        force_update: bool,
+    ) -> Result<(TaskUpdate, Vec<FieldConflict>)> {
+        // Without a base (or when forced) every field counts as changed in Linear
+        let mut changes = match (&mapping.last_projection, force_update) {
+            (Some(base), false) => projection.changes_since(base)?,
+            _ => projection.clone(),
         };
 
-        // Update the task in Motion
-        let updated_task = motion_client
-            .update_task(motion_task_id, &motion_task)
-            .await?;
-        debug!(
-            "Updated Motion task: {} ({})",
-            updated_task.name, motion_task_id
-        );
+        // Our labels are added to the task's own, e.g. on an adopted task, never replace them
+        if let (Some(labels), Some(task)) = (&mut changes.labels, motion_task) {
+            for label in task.labels.iter().flatten() {
+                if !labels.contains(&label.name) {
+                    labels.push(label.name.clone());
+                }
+            }
+        }
+
+        // Edited since our last sync, or still holding a value other than the one we last wrote
+        let current = motion_task.map(MotionSnapshot::from);
+        let mut edited = match (&mapping.motion_snapshot, &current) {
+            (Some(snapshot), Some(current)) => snapshot.edited_fields(current),
+            _ => Vec::new(),
+        };
+        if let (Some(base), Some(current)) = (&mapping.last_projection, &current) {
+            for field in current.edited_since(base) {
+                if !edited.contains(&field) {
+                    edited.push(field);
+                }
+            }
+        }
+        let contested: Vec<String> = changes
+            .fields()
+            .into_iter()
+            .filter(|field| edited.contains(field))
+            .collect();
+
+        if contested.is_empty() || policy == ConflictPolicy::LinearWins {
+            return Ok((changes, Vec::new()));
+        }
+
+        let conflicts = match policy {
+            ConflictPolicy::Report => contested
+                .iter()
+                .map(|field| FieldConflict {
+                    field: field.clone(),
+                    linear_value: changes.field_value(field).unwrap_or_default(),
+                    detected_at: chrono::Utc::now(),
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
 
-        Ok(updated_task)
+        Ok((changes.without(&contested)?, conflicts))
     }
 
     async fn create_motion_task_from_issue(
         motion_client: &MotionClient,
         issue: &crate::clients::linear::LinearIssue,
         sync_rules: &SyncRules,
+        timezone: Tz,
+        first_synced_at: chrono::DateTime<chrono::Utc>,
         sync_source_name: &str,
     ) -> Result<MotionTask> {
-        // Get Motion workspaces to determine where to create the task
-        let workspaces = motion_client.list_workspaces().await?;
-
-        // Find "My Private Workspace" or use the first available workspace
-        let workspace = workspaces
-            .iter()
-            .find(|w| w.name == "My Private Workspace")
-            .or_else(|| workspaces.first())
-            .ok_or_else(|| Error::MotionApi {
-                message: "No Motion workspaces found".to_string(),
-            })?;
+        // Get the Motion workspace to create the task in
+        let workspace = Self::target_workspace(motion_client).await?;
 
         // Ensure the "linear-sync" label exists in the workspace
         // self.motion_client
         //     .ensure_label_exists(&workspace.id, "linear-sync")
         //     .await?;
 
-        // Calculate duration from Linear estimate
-        let duration_mins = if let Some(estimate) = issue.estimate {
-            sync_rules
-                .time_estimate_strategy
-                .convert_estimate_by_value(estimate)
-                .unwrap_or(sync_rules.default_task_duration_mins)
-        } else {
-            sync_rules.default_task_duration_mins
-        };
+        let duration_mins = Self::duration_mins(issue, sync_rules);
 
-        // Convert priority (Linear uses 0-4, Motion uses ASAP/HIGH/MEDIUM/LOW)
-        let priority = match issue.priority {
-            Some(1) => Some("ASAP".to_string()),
-            Some(2) => Some("HIGH".to_string()),
-            Some(3) => Some("MEDIUM".to_string()),
-            Some(4) | Some(_) => Some("LOW".to_string()),
-            None => Some("MEDIUM".to_string()),
-        };
+        let priority = Some(Self::motion_priority(issue, sync_rules));
 
-        // Convert due_date string to DateTime if present
-        let due_date = issue
-            .due_date
-            .as_ref()
-            .and_then(|date_str| {
-                // Linear sends dates as YYYY-MM-DD, convert to DateTime
-                chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-                    .ok()
-                    .map(|date| date.and_hms_opt(23, 59, 59).unwrap().and_utc())
-            })
-            .unwrap_or(
-                chrono::Utc::now()
-                    .checked_add_days(Days::new(1))
-                    .expect("valid"),
-            );
+        let due_date = Self::deadline(issue, sync_rules, timezone, first_synced_at);
 
         // Create Motion task
         let motion_task = MotionTask {
@@ -753,18 +2635,17 @@ impl SyncOrchestrator {
                 name: workspace.name.clone(),
                 team_id: None,
                 workspace_type: "INDIVIDUAL".to_string(),
+                statuses: None,
             }),
-            auto_scheduled: Some(AutoScheduled {
-                deadline_type: "SOFT".to_string(),
-                schedule: "Work hours".to_string(),
-                start_date: Some(chrono::Utc::now()),
-            }),
-            status: Some(Status {
-                name: "Todo".to_string(),
-                ..Default::default()
-            }),
+            auto_scheduled: Some(Self::auto_scheduled(
+                issue,
+                sync_rules,
+                due_date,
+                first_synced_at,
+            )),
+            status: Some(Self::motion_status(issue, sync_rules)),
             priority,
-            due_date: Some(due_date),
+            due_date,
 
             completed: Some(false),
             labels: Some(vec![Label {
@@ -784,3 +2665,574 @@ impl SyncOrchestrator {
         Ok(created_task)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::clients::testing::{issue, task};
+    use crate::sync::adopt::MatchReason;
+
+    /// A config with one "personal" source and its database in a fresh temporary directory
+    fn config() -> AppConfig {
+        let path = std::env::temp_dir().join(format!("linear-motion-{}", uuid::Uuid::new_v4()));
+        serde_json::from_value(serde_json::json!({
+            "motion_api_key": "key",
+            "sync_sources": [{ "name": "personal", "linear_api_key": "key", "projects": null, "webhook_base_url": null, "sync_rules": null }],
+            "global_sync_rules": {
+                "default_task_duration_mins": 60,
+                "completed_linear_tag": "motioned",
+                "time_estimate_strategy": { "fibonacci": null, "tshirt": null, "linear": null, "points": null, "default_duration_mins": 60 }
+            },
+            "database_path": path.join("db"),
+            "polling_interval_seconds": 300,
+            "schedule_overrides": null,
+            "timezone": "UTC"
+        }))
+        .unwrap()
+    }
+
+    fn in_cycle(
+        issue: LinearIssue,
+        starts_at: chrono::DateTime<chrono::Utc>,
+        ends_at: chrono::DateTime<chrono::Utc>,
+    ) -> LinearIssue {
+        LinearIssue {
+            cycle: Some(crate::clients::linear::Cycle {
+                id: "cycle-1".to_string(),
+                number: 1.0,
+                starts_at,
+                ends_at,
+            }),
+            ..issue
+        }
+    }
+
+    #[test]
+    fn test_cycle_rules() {
+        use crate::config::DefaultDeadlinePolicy;
+        use chrono::TimeZone;
+
+        let utc = |d, h| chrono::Utc.with_ymd_and_hms(2025, 3, d, h, 0, 0).unwrap();
+        let mut rules = config().global_sync_rules;
+        rules.due_time = "17:00".to_string();
+        let first_synced_at = utc(3, 9);
+        let issue = in_cycle(issue("issue-1", "CAR-1", "Ship it"), utc(10, 0), utc(24, 0));
+
+        // The cycle end applies on the cycle's last day, at the due time
+        assert_eq!(
+            SyncOrchestrator::deadline(&issue, &rules, Tz::UTC, first_synced_at),
+            Some(utc(23, 17))
+        );
+
+        // Policies listed before the cycle end win, including `none`
+        let with_project = LinearIssue {
+            project: Some(crate::clients::linear::Project {
+                id: "project-1".to_string(),
+                name: "Launch".to_string(),
+                description: None,
+                state: "started".to_string(),
+                target_date: Some("2025-03-31".to_string()),
+            }),
+            ..issue.clone()
+        };
+        let project_first = SyncRules {
+            default_deadline: vec![
+                DefaultDeadlinePolicy::ProjectTargetDate,
+                DefaultDeadlinePolicy::CycleEnd,
+            ],
+            ..rules.clone()
+        };
+        assert_eq!(
+            SyncOrchestrator::deadline(&with_project, &project_first, Tz::UTC, first_synced_at),
+            Some(utc(31, 17))
+        );
+        let no_deadline = SyncRules {
+            default_deadline: vec![DefaultDeadlinePolicy::None],
+            ..rules.clone()
+        };
+        assert_eq!(
+            SyncOrchestrator::deadline(&issue, &no_deadline, Tz::UTC, first_synced_at),
+            None
+        );
+
+        // Scheduling starts with the cycle, and leaving the cycle moves the start back
+        let projection =
+            SyncOrchestrator::task_projection(&issue, &rules, Tz::UTC, first_synced_at, "personal");
+        assert_eq!(
+            projection.auto_scheduled.as_ref().unwrap().start_date,
+            Some(utc(10, 0))
+        );
+        let left_cycle = LinearIssue {
+            cycle: None,
+            ..issue.clone()
+        };
+        let moved = SyncOrchestrator::task_projection(
+            &left_cycle,
+            &rules,
+            Tz::UTC,
+            first_synced_at,
+            "personal",
+        );
+        assert_eq!(
+            moved.auto_scheduled.as_ref().unwrap().start_date,
+            Some(first_synced_at)
+        );
+        assert!(moved
+            .changes_since(&projection)
+            .unwrap()
+            .fields()
+            .contains(&"autoScheduled".to_string()));
+
+        // Issues are only held back for future cycles, and only when configured
+        let now = chrono::Utc::now();
+        let future = in_cycle(
+            issue.clone(),
+            now + chrono::Duration::days(7),
+            now + chrono::Duration::days(21),
+        );
+        assert_eq!(SyncOrchestrator::held_until(&future, &rules), None);
+        rules.cycles.hold_until_cycle_start = true;
+        assert_eq!(
+            SyncOrchestrator::held_until(&future, &rules),
+            future.cycle.as_ref().map(|c| c.starts_at)
+        );
+        assert_eq!(SyncOrchestrator::held_until(&issue, &rules), None);
+    }
+
+    #[test]
+    fn test_unmapped_states_fall_back_to_todo() {
+        let mut rules = config().global_sync_rules;
+        rules
+            .status_map
+            .by_type
+            .insert("started".to_string(), "In Progress".to_string());
+
+        let mut started = issue("issue-1", "CAR-1", "Ship it");
+        started.state.state_type = "started".to_string();
+        assert_eq!(
+            SyncOrchestrator::motion_status(&started, &rules).name,
+            "In Progress"
+        );
+        assert_eq!(
+            SyncOrchestrator::motion_status(&issue("issue-2", "CAR-2", "Ship it"), &rules).name,
+            "Todo"
+        );
+    }
+
+    fn workspace(statuses: &[&str]) -> MotionWorkspace {
+        MotionWorkspace {
+            id: "ws-1".to_string(),
+            name: "My Private Workspace".to_string(),
+            team_id: None,
+            workspace_type: "INDIVIDUAL".to_string(),
+            statuses: Some(
+                statuses
+                    .iter()
+                    .map(|name| Status {
+                        name: name.to_string(),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_motion_edits_survive_a_quiet_sync() {
+        let rules = config().global_sync_rules;
+        let first_synced_at = chrono::Utc::now();
+        let projection = |issue: &LinearIssue| {
+            SyncOrchestrator::task_projection(
+                issue,
+                &rules,
+                chrono_tz::UTC,
+                first_synced_at,
+                "personal",
+            )
+        };
+        let merge = |mapping: &TaskMapping, linear: &LinearIssue, task: &MotionTask, policy| {
+            SyncOrchestrator::merge_update(&projection(linear), mapping, Some(task), policy, false)
+                .unwrap()
+        };
+        let priority = "priority".to_string();
+
+        let mut linear = issue("issue-1", "CAR-1", "Ship it");
+        linear.priority = Some(3);
+        let written = projection(&linear);
+        let mut mapping = TaskMapping::new(
+            "issue-1".to_string(),
+            "tk_1".to_string(),
+            "personal".to_string(),
+        );
+        mapping.last_projection = Some(written.clone());
+
+        // The task as written, then with its priority edited in Motion
+        let mut motion = task("tk_1", written.name.as_deref().unwrap());
+        motion.duration = written.duration.clone();
+        motion.priority = written.priority.clone();
+        motion.due_date = written.due_date;
+        mapping.motion_snapshot = Some(MotionSnapshot::from(&motion));
+        motion.priority = Some("HIGH".to_string());
+        motion.updated_time = Some(chrono::Utc::now());
+
+        // A sync without Linear changes leaves the edit alone and snapshots it
+        let (update, conflicts) = merge(&mapping, &linear, &motion, ConflictPolicy::Report);
+        assert!(!update.fields().contains(&priority));
+        assert!(conflicts.is_empty());
+        mapping.motion_snapshot = Some(MotionSnapshot::from(&motion));
+
+        // A later Linear change to the field still can't overwrite it
+        linear.priority = Some(1);
+        let (update, conflicts) = merge(&mapping, &linear, &motion, ConflictPolicy::Report);
+        assert!(!update.fields().contains(&priority));
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].field, priority);
+
+        let (update, conflicts) = merge(&mapping, &linear, &motion, ConflictPolicy::KeepMotion);
+        assert!(!update.fields().contains(&priority));
+        assert!(conflicts.is_empty());
+
+        let (update, _) = merge(&mapping, &linear, &motion, ConflictPolicy::LinearWins);
+        assert!(update.fields().contains(&priority));
+    }
+
+    #[test]
+    fn test_adopted_task_keeps_its_labels() {
+        let rules = config().global_sync_rules;
+        let linear = issue("issue-1", "CAR-1", "Ship it");
+        let projection = SyncOrchestrator::task_projection(
+            &linear,
+            &rules,
+            chrono_tz::UTC,
+            chrono::Utc::now(),
+            "personal",
+        );
+
+        // Adopted mappings have no projection yet, so the whole projection is written
+        let mut motion = task("tk_1", "[CAR-1] Ship it");
+        motion.labels = Some(vec![
+            Label {
+                name: "errands".to_string(),
+            },
+            Label {
+                name: "linear-sync".to_string(),
+            },
+        ]);
+        let mut mapping = TaskMapping::new(
+            "issue-1".to_string(),
+            "tk_1".to_string(),
+            "personal".to_string(),
+        );
+        mapping.motion_snapshot = Some(MotionSnapshot::from(&motion));
+
+        let (update, _) = SyncOrchestrator::merge_update(
+            &projection,
+            &mapping,
+            Some(&motion),
+            ConflictPolicy::Report,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            update.labels,
+            Some(vec!["linear-sync".to_string(), "errands".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_terminal_actions() {
+        let rules = config().global_sync_rules;
+        let mut done = issue("issue-1", "CAR-1", "Ship it");
+        done.state.state_type = "completed".to_string();
+        let mut canceled = done.clone();
+        canceled.state.state_type = "canceled".to_string();
+        let mut duplicate = canceled.clone();
+        duplicate.state.name = "Duplicate".to_string();
+
+        assert_eq!(
+            SyncOrchestrator::terminal_action(&done, &rules),
+            TerminalAction::Complete
+        );
+        assert_eq!(
+            SyncOrchestrator::terminal_action(&canceled, &rules),
+            TerminalAction::Complete
+        );
+        assert_eq!(
+            SyncOrchestrator::terminal_action(&duplicate, &rules),
+            TerminalAction::Delete
+        );
+
+        // The defaults only need Motion's completed status
+        assert!(
+            SyncOrchestrator::validate_statuses(&workspace(&["Todo", "Completed"]), &rules).is_ok()
+        );
+        assert!(matches!(
+            SyncOrchestrator::validate_statuses(&workspace(&["Todo"]), &rules),
+            Err(Error::Validation(_))
+        ));
+
+        // Archiving needs the archive status, and nothing needs "Completed" once it's unused
+        let mut archive = rules.clone();
+        archive.terminal_states.completed = TerminalAction::Archive;
+        archive.terminal_states.canceled = TerminalAction::Archive;
+        assert_eq!(
+            SyncOrchestrator::terminal_action(&canceled, &archive),
+            TerminalAction::Archive
+        );
+        assert!(matches!(
+            SyncOrchestrator::validate_statuses(&workspace(&["Todo", "Completed"]), &archive),
+            Err(Error::Validation(_))
+        ));
+        assert!(
+            SyncOrchestrator::validate_statuses(&workspace(&["Todo", "Canceled"]), &archive)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_reopen_detection() {
+        let mut done = issue("issue-1", "CAR-1", "Ship it");
+        done.state.state_type = "completed".to_string();
+        let mut mapping = crate::db::mapping::TaskMapping::new(
+            "issue-1".to_string(),
+            "tk_1".to_string(),
+            "personal".to_string(),
+        );
+        mapping.linear_issue_data = serde_json::to_value(&done).unwrap();
+
+        let reopened = issue("issue-1", "CAR-1", "Ship it");
+        assert!(SyncOrchestrator::was_reopened(&mapping, &reopened).unwrap());
+        assert!(!SyncOrchestrator::was_reopened(&mapping, &done).unwrap());
+
+        // Only issues last seen in a terminal state count as reopened
+        mapping.linear_issue_data = serde_json::to_value(&reopened).unwrap();
+        assert!(!SyncOrchestrator::was_reopened(&mapping, &reopened).unwrap());
+
+        mapping.linear_issue_data = serde_json::json!({ "identifier": "CAR-1" });
+        assert!(SyncOrchestrator::was_reopened(&mapping, &reopened).is_err());
+    }
+
+    #[test]
+    fn test_schedule_feedback_text() {
+        use chrono::TimeZone;
+
+        let at = |d, h| chrono::Utc.with_ymd_and_hms(2025, 3, d, h, 0, 0).unwrap();
+        let chunk = |id: &str, start, end| crate::clients::motion::TaskChunk {
+            id: id.to_string(),
+            duration: 60,
+            scheduled_start: Some(start),
+            scheduled_end: Some(end),
+            completed_time: None,
+            is_fixed: false,
+        };
+
+        // Without chunks the task's own schedule is used, converted to local time
+        let mut planned = task("tk_1", "[CAR-1] Ship it");
+        planned.scheduled_start = Some(at(4, 13));
+        planned.scheduled_end = Some(at(4, 15));
+        let berlin: Tz = "Europe/Berlin".parse().unwrap();
+        assert_eq!(
+            SyncOrchestrator::scheduled_blocks(&planned, berlin),
+            vec!["Tue 4 Mar 14:00–16:00"]
+        );
+
+        // Chunks are listed in order, with both days shown for blocks spanning midnight
+        planned.chunks = Some(vec![
+            chunk("c2", at(5, 23), at(6, 1)),
+            chunk("c1", at(4, 9), at(4, 10)),
+        ]);
+        let blocks = SyncOrchestrator::scheduled_blocks(&planned, Tz::UTC);
+        assert_eq!(
+            blocks,
+            vec!["Tue 4 Mar 09:00–10:00", "Wed 5 Mar 23:00 – Thu 6 Mar 01:00"]
+        );
+        assert_eq!(
+            SyncOrchestrator::schedule_title(&blocks),
+            "Scheduled in Motion: Tue 4 Mar 09:00–10:00, Wed 5 Mar 23:00 – Thu 6 Mar 01:00"
+        );
+        assert_eq!(
+            SyncOrchestrator::schedule_comment(&blocks),
+            "**Motion schedule:**\n\n- Tue 4 Mar 09:00–10:00\n- Wed 5 Mar 23:00 – Thu 6 Mar 01:00"
+        );
+
+        let unplanned = SyncOrchestrator::scheduled_blocks(&task("tk_2", "[CAR-2] Later"), Tz::UTC);
+        assert!(unplanned.is_empty());
+        assert_eq!(
+            SyncOrchestrator::schedule_title(&unplanned),
+            "Not yet scheduled in Motion"
+        );
+        assert_eq!(
+            SyncOrchestrator::schedule_comment(&unplanned),
+            "**Motion schedule:** not yet scheduled"
+        );
+    }
+
+    #[test]
+    fn test_guarded_cleanup_deletions() {
+        let mapping = |id: &str, status: MappingStatus| {
+            let mut mapping =
+                TaskMapping::new(id.to_string(), format!("tk_{}", id), "personal".to_string());
+            mapping.status = status;
+            mapping
+        };
+        let mut done = issue("issue-3", "CAR-3", "Ship it");
+        done.state.state_type = "completed".to_string();
+        let orphaned_issues = HashMap::from([("issue-3".to_string(), done)]);
+
+        // Unassigned issues lose their task, terminal ones and completed mappings don't
+        let unassigned = mapping("issue-1", MappingStatus::Synced);
+        let completed = mapping("issue-2", MappingStatus::Completed);
+        let terminal = mapping("issue-3", MappingStatus::Synced);
+        let mut never_created = mapping("issue-4", MappingStatus::Pending);
+        never_created.motion_task_id = None;
+        assert!(SyncOrchestrator::cleanup_deletes(
+            &unassigned,
+            &orphaned_issues
+        ));
+        assert!(!SyncOrchestrator::cleanup_deletes(
+            &completed,
+            &orphaned_issues
+        ));
+        assert!(!SyncOrchestrator::cleanup_deletes(
+            &terminal,
+            &orphaned_issues
+        ));
+        assert!(!SyncOrchestrator::cleanup_deletes(
+            &never_created,
+            &orphaned_issues
+        ));
+
+        // With only a couple of synced tasks a single unassignment still goes through
+        let guard = config().global_sync_rules.deletion_guard;
+        assert!(guard.allows(1, 2));
+        assert!(!guard.allows(8, 10));
+    }
+
+    #[tokio::test]
+    async fn test_create_intent_recovery() {
+        let config = config();
+        let orchestrator = SyncOrchestrator::new(&config).await.unwrap();
+        let mappings = &orchestrator.database.mappings;
+        let synced = |id: &str, name: &str| MotionTask {
+            labels: Some(vec![Label {
+                name: "linear-sync".to_string(),
+            }]),
+            ..task(id, name)
+        };
+
+        mappings
+            .create_pending_mapping("personal", &issue("issue-1", "CAR-1", "Ship it"))
+            .await
+            .unwrap();
+        mappings
+            .record_create_intent("personal", "issue-1", "CAR-1")
+            .await
+            .unwrap();
+        let mapping = mappings
+            .get_mapping_by_linear_id("personal", "issue-1")
+            .await
+            .unwrap()
+            .unwrap();
+
+        // A task already linked to another issue is not taken over
+        let mut other = TaskMapping::new(
+            "issue-9".to_string(),
+            "tk_owned".to_string(),
+            "personal".to_string(),
+        );
+        other.set_synced("tk_owned".to_string());
+        mappings.store_mapping(other).await.unwrap();
+
+        let mut tasks: HashMap<String, MotionTask> = [
+            synced("tk_owned", "[CAR-1] Ship it"),
+            synced("tk_other", "[CAR-10] Something else"),
+            task("tk_unlabelled", "[CAR-1] Ship it"),
+        ]
+        .into_iter()
+        .map(|task| (task.id.clone().unwrap(), task))
+        .collect();
+        let database = &orchestrator.database;
+        assert!(
+            SyncOrchestrator::recover_created_task(database, &mapping, &tasks)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        tasks.insert("tk_new".to_string(), synced("tk_new", "[CAR-1] Ship it"));
+        let recovered = SyncOrchestrator::recover_created_task(database, &mapping, &tasks)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(recovered.id.as_deref(), Some("tk_new"));
+    }
+
+    #[tokio::test]
+    async fn test_restore_refuses_linked_issues() {
+        let config = config();
+        let orchestrator = SyncOrchestrator::new(&config).await.unwrap();
+        let mappings = &orchestrator.database.mappings;
+
+        let mut old = TaskMapping::new(
+            "issue-1".to_string(),
+            "tk_deleted".to_string(),
+            "personal".to_string(),
+        );
+        old.linear_issue_data = serde_json::json!({ "identifier": "CAR-1" });
+        mappings
+            .store_tombstone(&Tombstone {
+                motion_task: task("tk_deleted", "[CAR-1] Ship it"),
+                mapping: Some(old.clone()),
+                reason: "Linear issue no longer assigned".to_string(),
+                deleted_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        // The issue got a new task since, restoring would leave it with two
+        let mut relinked = old;
+        relinked.set_synced("tk_new".to_string());
+        mappings.store_mapping(relinked).await.unwrap();
+
+        assert!(matches!(
+            orchestrator.restore(&config, "car-1").await,
+            Err(Error::Validation(_))
+        ));
+        assert!(mappings
+            .get_tombstone("tk_deleted")
+            .await
+            .unwrap()
+            .is_some());
+        assert!(matches!(
+            orchestrator.restore(&config, "CAR-2").await,
+            Err(Error::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_adopt_requires_task_id() {
+        let config = config();
+        let orchestrator = SyncOrchestrator::new(&config).await.unwrap();
+        let candidate = AdoptionCandidate {
+            sync_source: "personal".to_string(),
+            issue: issue("issue-1", "CAR-1", "Ship it"),
+            task: MotionTask {
+                id: None,
+                ..task("", "Ship it")
+            },
+            reason: MatchReason::Identifier,
+        };
+
+        assert!(matches!(
+            orchestrator.adopt(&candidate).await,
+            Err(Error::Sync(_))
+        ));
+        assert!(orchestrator
+            .database
+            .mappings
+            .get_mapping_by_linear_id("personal", "issue-1")
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,211 @@
+use crate::clients::{linear::LinearIssue, motion::MotionTask};
+use crate::db::mapping::TaskMapping;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// What reconciling decided for a Motion task carrying the sync label
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconcileAction {
+    /// Already mapped to its issue
+    Mapped,
+    /// Matched to an issue without a mapping pointing at it
+    Adopted,
+    /// Another task already stands for the same issue
+    Duplicate,
+    /// No current Linear issue matches the task
+    Orphaned,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconcileEntry {
+    pub motion_task_id: String,
+    pub task_name: String,
+    pub identifier: Option<String>,
+    /// The sync source and Linear issue id the task was matched to
+    pub sync_source: Option<String>,
+    pub linear_issue_id: Option<String>,
+    pub action: ReconcileAction,
+    /// Whether the action was carried out, false for dry runs and reports
+    pub applied: bool,
+}
+
+/// Match synced Motion tasks to the current Linear issues of every source.
+///
+/// Tasks a mapping points at are always kept, even when their issue is no longer assigned, since
+/// cleanup and the terminal state rules decide what happens to those. For each issue the task
+/// its mapping already points at is kept, otherwise the oldest one.
+pub fn plan(
+    tasks: &[MotionTask],
+    issues: &[(String, LinearIssue)],
+    mappings: &[TaskMapping],
+) -> Vec<ReconcileEntry> {
+    let mapping_for = |task_id: Option<&str>| {
+        mappings
+            .iter()
+            .find(|mapping| task_id.is_some() && mapping.motion_task_id.as_deref() == task_id)
+    };
+
+    let mut by_identifier: BTreeMap<Option<String>, Vec<&MotionTask>> = BTreeMap::new();
+    for task in tasks.iter().filter(|task| task.id.is_some()) {
+        by_identifier
+            .entry(task.linear_identifier())
+            .or_default()
+            .push(task);
+    }
+
+    let mut entries = Vec::new();
+    for (identifier, group) in by_identifier {
+        let issue = identifier.as_ref().and_then(|identifier| {
+            issues
+                .iter()
+                .find(|(_, issue)| issue.identifier == *identifier)
+        });
+        let entry = |task: &MotionTask, matched: Option<(&str, &str)>, action| ReconcileEntry {
+            motion_task_id: task.id.clone().unwrap_or_default(),
+            task_name: task.name.clone(),
+            identifier: identifier.clone(),
+            sync_source: matched.map(|(source, _)| source.to_string()),
+            linear_issue_id: matched.map(|(_, issue_id)| issue_id.to_string()),
+            action,
+            applied: false,
+        };
+
+        let (mapped, mut unmapped): (Vec<_>, Vec<_>) = group
+            .into_iter()
+            .partition(|task| mapping_for(task.id.as_deref()).is_some());
+        for task in mapped {
+            let mapping = mapping_for(task.id.as_deref()).unwrap();
+            let matched = (
+                mapping.sync_source.as_str(),
+                mapping.linear_issue_id.as_str(),
+            );
+            entries.push(entry(task, Some(matched), ReconcileAction::Mapped));
+        }
+
+        let Some((source, issue)) = issue else {
+            entries.extend(
+                unmapped
+                    .iter()
+                    .map(|task| entry(task, None, ReconcileAction::Orphaned)),
+            );
+            continue;
+        };
+
+        // An issue whose mapped task is still open keeps it, otherwise the oldest task is adopted
+        let has_task = mappings
+            .iter()
+            .find(|mapping| mapping.sync_source == *source && mapping.linear_issue_id == issue.id)
+            .and_then(|mapping| mapping.motion_task_id.as_deref())
+            .is_some_and(|id| tasks.iter().any(|task| task.id.as_deref() == Some(id)));
+        unmapped.sort_by_key(|task| {
+            (
+                task.created_time.is_none(),
+                task.created_time,
+                task.id.clone(),
+            )
+        });
+
+        let matched = (source.as_str(), issue.id.as_str());
+        for (index, task) in unmapped.iter().enumerate() {
+            let action = match index {
+                0 if !has_task => ReconcileAction::Adopted,
+                _ => ReconcileAction::Duplicate,
+            };
+            entries.push(entry(task, Some(matched), action));
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::clients::testing;
+    use chrono::{DateTime, Utc};
+
+    fn task(id: &str, name: &str, created: &str) -> MotionTask {
+        MotionTask {
+            created_time: Some(created.parse::<DateTime<Utc>>().unwrap()),
+            ..testing::task(id, name)
+        }
+    }
+
+    fn issue(id: &str, identifier: &str) -> LinearIssue {
+        testing::issue(id, identifier, "Ship it")
+    }
+
+    #[test]
+    fn test_plan_reconcile() {
+        let tasks = vec![
+            task("tk_new", "[CAR-1] Ship it", "2025-02-02T00:00:00Z"),
+            task("tk_old", "[CAR-1] Ship it", "2025-02-01T00:00:00Z"),
+            task("tk_gone", "[CAR-9] Old work", "2025-02-01T00:00:00Z"),
+            task("tk_two", "[CAR-2] Ship it", "2025-02-01T00:00:00Z"),
+        ];
+        let issues = vec![
+            ("personal".to_string(), issue("issue-1", "CAR-1")),
+            ("personal".to_string(), issue("issue-2", "CAR-2")),
+        ];
+        let mappings = vec![TaskMapping::new(
+            "issue-2".to_string(),
+            "tk_two".to_string(),
+            "personal".to_string(),
+        )];
+
+        let actions: Vec<_> = plan(&tasks, &issues, &mappings)
+            .into_iter()
+            .map(|entry| (entry.motion_task_id, entry.action))
+            .collect();
+
+        assert_eq!(
+            actions,
+            vec![
+                ("tk_old".to_string(), ReconcileAction::Adopted),
+                ("tk_new".to_string(), ReconcileAction::Duplicate),
+                ("tk_two".to_string(), ReconcileAction::Mapped),
+                ("tk_gone".to_string(), ReconcileAction::Orphaned),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mapped_tasks_are_never_orphaned() {
+        let tasks = vec![
+            // Adopted by title, so it has no identifier prefix yet
+            task("tk_adopted", "Ship it", "2025-02-01T00:00:00Z"),
+            // Its issue was unassigned and cleanup is holding the deletion
+            task("tk_held", "[CAR-9] Old work", "2025-02-01T00:00:00Z"),
+            task("tk_stray", "[CAR-9] Old work", "2025-02-02T00:00:00Z"),
+        ];
+        let issues = vec![("personal".to_string(), issue("issue-1", "CAR-1"))];
+        let mappings = vec![
+            TaskMapping::new(
+                "issue-1".to_string(),
+                "tk_adopted".to_string(),
+                "personal".to_string(),
+            ),
+            TaskMapping::new(
+                "issue-9".to_string(),
+                "tk_held".to_string(),
+                "personal".to_string(),
+            ),
+        ];
+
+        let entries = plan(&tasks, &issues, &mappings);
+        let actions: Vec<_> = entries
+            .iter()
+            .map(|entry| (entry.motion_task_id.as_str(), entry.action))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("tk_adopted", ReconcileAction::Mapped),
+                ("tk_held", ReconcileAction::Mapped),
+                ("tk_stray", ReconcileAction::Orphaned),
+            ]
+        );
+        assert_eq!(entries[1].linear_issue_id.as_deref(), Some("issue-9"));
+    }
+}